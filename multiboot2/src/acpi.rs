@@ -0,0 +1,508 @@
+//! Module for the acpi-feature: walking the ACPI tables that the RSDP of the
+//! [`crate::RsdpV1Tag`] or the [`crate::RsdpV2Tag`] points to.
+//!
+//! The tables are located at physical addresses. The caller provides a closure
+//! that maps a physical address to a virtual address that is valid for
+//! reading. The tables are located via the XSDT, if the RSDP of ACPI 2.0 or
+//! later provides one, and via the RSDT otherwise.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use multiboot2::acpi::AcpiTables;
+//!
+//! let tables = unsafe { AcpiTables::new(&boot_info, |phys| phys as usize + PHYS_OFFSET) }.unwrap();
+//! for header in tables.headers() {
+//!     println!("{:?}", header);
+//! }
+//! let madt = tables.madt().unwrap();
+//! ```
+
+use crate::{BootInformation, RsdpError};
+use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::ptr;
+use core::slice;
+
+/// The signature of the Multiple APIC Description Table (MADT).
+pub const MADT_SIGNATURE: [u8; 4] = *b"APIC";
+
+/// The signature of the Fixed ACPI Description Table (FADT).
+pub const FADT_SIGNATURE: [u8; 4] = *b"FACP";
+
+/// The signature of the High Precision Event Timer Table (HPET).
+pub const HPET_SIGNATURE: [u8; 4] = *b"HPET";
+
+/// The signature of the PCI Express Memory-mapped Configuration Space base
+/// address description table (MCFG).
+pub const MCFG_SIGNATURE: [u8; 4] = *b"MCFG";
+
+const RSDT_SIGNATURE: [u8; 4] = *b"RSDT";
+const XSDT_SIGNATURE: [u8; 4] = *b"XSDT";
+
+/// Errors that can occur while locating the root table of the ACPI tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AcpiError {
+    /// The boot information has neither a [`crate::RsdpV1Tag`] nor a
+    /// [`crate::RsdpV2Tag`].
+    NoRsdp,
+    /// The RSDP is not valid, see [`crate::Rsdp::validate`].
+    InvalidRsdp(RsdpError),
+    /// The signature of the root table is not `"RSDT"` or `"XSDT"`,
+    /// respectively. Holds the signature.
+    InvalidSignature([u8; 4]),
+    /// The length of the root table is smaller than its header. Holds the
+    /// length.
+    InvalidLength(u32),
+    /// The checksum of the root table is not valid.
+    InvalidChecksum,
+}
+
+/// The header that all System Description Tables (SDTs) share.
+///
+/// A header only exists as a reference into a mapped table, as
+/// [`SdtHeader::bytes`] reads the rest of the table behind it.
+#[repr(C, packed)]
+pub struct SdtHeader {
+    signature: [u8; 4],
+    length: u32,
+    revision: u8,
+    checksum: u8,
+    oem_id: [u8; 6],
+    oem_table_id: [u8; 8],
+    oem_revision: u32,
+    creator_id: u32,
+    creator_revision: u32,
+}
+
+impl SdtHeader {
+    /// The signature of the table, such as `*b"APIC"` for the MADT.
+    pub fn signature(&self) -> [u8; 4] {
+        self.signature
+    }
+
+    /// The length of the table in bytes, including the header.
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// The revision of the structure of the table.
+    pub fn revision(&self) -> u8 {
+        self.revision
+    }
+
+    /// An OEM-supplied string that identifies the OEM.
+    pub fn oem_id(&self) -> [u8; 6] {
+        self.oem_id
+    }
+
+    /// An OEM-supplied string that identifies the particular data table.
+    pub fn oem_table_id(&self) -> [u8; 8] {
+        self.oem_table_id
+    }
+
+    /// An OEM-supplied revision number.
+    pub fn oem_revision(&self) -> u32 {
+        self.oem_revision
+    }
+
+    /// The vendor ID of the utility that created the table.
+    pub fn creator_id(&self) -> u32 {
+        self.creator_id
+    }
+
+    /// The revision of the utility that created the table.
+    pub fn creator_revision(&self) -> u32 {
+        self.creator_revision
+    }
+
+    /// Returns the whole table, including the header, as bytes. Returns
+    /// `None`, if the length is smaller than the header.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        if (self.length as usize) < size_of::<Self>() {
+            return None;
+        }
+        // SAFETY: The table is mapped completely, see `AcpiTables::new`.
+        Some(unsafe {
+            slice::from_raw_parts(self as *const Self as *const u8, self.length as usize)
+        })
+    }
+
+    /// Validation of the checksum of the whole table.
+    pub fn checksum_is_valid(&self) -> bool {
+        self.bytes()
+            .map(|bytes| bytes.iter().fold(0u8, |acc, val| acc.wrapping_add(*val)) == 0)
+            .unwrap_or(false)
+    }
+}
+
+impl Debug for SdtHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SdtHeader")
+            .field("signature", &self.signature)
+            .field("length", &{ self.length })
+            .field("revision", &self.revision)
+            .field("checksum", &self.checksum)
+            .field("oem_id", &self.oem_id)
+            .field("oem_table_id", &self.oem_table_id)
+            .field("oem_revision", &{ self.oem_revision })
+            .field("creator_id", &{ self.creator_id })
+            .field("creator_revision", &{ self.creator_revision })
+            .finish()
+    }
+}
+
+/// The ACPI tables of the system, located via the RSDT or the XSDT.
+pub struct AcpiTables<F> {
+    root: *const SdtHeader,
+    entry_size: usize,
+    phys_to_virt: F,
+}
+
+impl<F: Fn(u64) -> usize> AcpiTables<F> {
+    /// Locates the root table (the XSDT or the RSDT) via the RSDP of the boot
+    /// information and validates the RSDP as well as the signature and checksum
+    /// of the root table. The XSDT is used, if the [`crate::RsdpV2Tag`] is
+    /// present and its XSDT address is set, and the RSDT otherwise.
+    ///
+    /// # Safety
+    /// `phys_to_virt` must map every physical address of an ACPI table to a
+    /// virtual address where the whole table is mapped and valid for reading,
+    /// for as long as the returned value is used.
+    pub unsafe fn new(boot_info: &BootInformation, phys_to_virt: F) -> Result<Self, AcpiError> {
+        let rsdp = boot_info.rsdp().ok_or(AcpiError::NoRsdp)?;
+        rsdp.validate().map_err(AcpiError::InvalidRsdp)?;
+        let (address, signature, entry_size) = match rsdp.xsdt_address() {
+            Some(address) => (address, XSDT_SIGNATURE, size_of::<u64>()),
+            None => (
+                u64::from(rsdp.rsdt_address()),
+                RSDT_SIGNATURE,
+                size_of::<u32>(),
+            ),
+        };
+
+        let root = phys_to_virt(address) as *const SdtHeader;
+        let header = &*root;
+        if header.signature != signature {
+            return Err(AcpiError::InvalidSignature(header.signature));
+        }
+        if (header.length as usize) < size_of::<SdtHeader>() {
+            return Err(AcpiError::InvalidLength(header.length));
+        }
+        if !header.checksum_is_valid() {
+            return Err(AcpiError::InvalidChecksum);
+        }
+        Ok(Self {
+            root,
+            entry_size,
+            phys_to_virt,
+        })
+    }
+
+    /// Returns the header of the root table, i.e. of the XSDT or the RSDT.
+    pub fn root(&self) -> &SdtHeader {
+        unsafe { &*self.root }
+    }
+
+    /// Returns an iterator over the headers of all tables that the root table
+    /// references. The headers are not validated: their length might be
+    /// smaller than the header and their checksum might be wrong. Use
+    /// [`SdtHeader::checksum_is_valid`], which checks both, before relying on
+    /// the content of a table, or use [`Self::find`].
+    pub fn headers(&self) -> SdtHeaderIter<'_, F> {
+        SdtHeaderIter {
+            tables: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the first table with the given signature, a valid length and a
+    /// valid checksum.
+    pub fn find(&self, signature: [u8; 4]) -> Option<&SdtHeader> {
+        self.headers()
+            .find(|header| header.signature == signature && header.checksum_is_valid())
+    }
+
+    /// Returns the Multiple APIC Description Table (MADT).
+    pub fn madt(&self) -> Option<&SdtHeader> {
+        self.find(MADT_SIGNATURE)
+    }
+
+    /// Returns the Fixed ACPI Description Table (FADT).
+    pub fn fadt(&self) -> Option<&SdtHeader> {
+        self.find(FADT_SIGNATURE)
+    }
+
+    /// Returns the High Precision Event Timer Table (HPET).
+    pub fn hpet(&self) -> Option<&SdtHeader> {
+        self.find(HPET_SIGNATURE)
+    }
+
+    /// Returns the PCI Express Memory-mapped Configuration Space base address
+    /// description table (MCFG).
+    pub fn mcfg(&self) -> Option<&SdtHeader> {
+        self.find(MCFG_SIGNATURE)
+    }
+
+    /// Returns the number of tables that the root table references.
+    fn entry_count(&self) -> usize {
+        (self.root().length as usize - size_of::<SdtHeader>()) / self.entry_size
+    }
+
+    /// Returns the physical address of the table with the given index.
+    fn entry(&self, index: usize) -> u64 {
+        unsafe {
+            let entry =
+                (self.root as *const u8).add(size_of::<SdtHeader>() + index * self.entry_size);
+            if self.entry_size == size_of::<u64>() {
+                ptr::read_unaligned(entry as *const u64)
+            } else {
+                ptr::read_unaligned(entry as *const u32).into()
+            }
+        }
+    }
+}
+
+impl<F: Fn(u64) -> usize> Debug for AcpiTables<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AcpiTables")
+            .field("root", self.root())
+            .field("headers", &self.headers())
+            .finish()
+    }
+}
+
+/// An iterator over the headers of the tables that the root table of the
+/// [`AcpiTables`] references.
+pub struct SdtHeaderIter<'a, F> {
+    tables: &'a AcpiTables<F>,
+    index: usize,
+}
+
+impl<F> Clone for SdtHeaderIter<'_, F> {
+    fn clone(&self) -> Self {
+        Self {
+            tables: self.tables,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, F: Fn(u64) -> usize> Iterator for SdtHeaderIter<'a, F> {
+    type Item = &'a SdtHeader;
+
+    fn next(&mut self) -> Option<&'a SdtHeader> {
+        if self.index >= self.tables.entry_count() {
+            return None;
+        }
+        let address = self.tables.entry(self.index);
+        self.index += 1;
+        Some(unsafe { &*((self.tables.phys_to_virt)(address) as *const SdtHeader) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tables.entry_count() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<F: Fn(u64) -> usize> ExactSizeIterator for SdtHeaderIter<'_, F> {}
+
+impl<F: Fn(u64) -> usize> Debug for SdtHeaderIter<'_, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|header| {
+            list.entry(header);
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AcpiError, AcpiTables, MADT_SIGNATURE};
+    use crate::{BootInformation, RsdpError, RsdpV1Tag, RsdpV2Tag, TagType};
+    use std::vec::Vec;
+
+    #[repr(C, align(8))]
+    struct Mbi([u8; 64]);
+
+    /// Returns a table with a valid checksum.
+    fn table(signature: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(signature);
+        bytes.extend(&(36 + body.len() as u32).to_ne_bytes());
+        bytes.extend(&[1, 0]); // revision, checksum
+        bytes.extend(b"OEMID OEMTABLE");
+        bytes.extend(&[0; 12]);
+        bytes.extend(body);
+        let sum = bytes.iter().fold(0u8, |acc, val| acc.wrapping_add(*val));
+        bytes[9] = 0u8.wrapping_sub(sum);
+        bytes
+    }
+
+    /// Returns the "physical memory" with the root table at `start`, followed
+    /// by a MADT, a FADT with an invalid checksum and a valid FADT.
+    fn physical_memory(root_signature: &[u8; 4], entry_size: usize, start: usize) -> Vec<u8> {
+        let madt = table(b"APIC", &[0; 8]);
+        let mut bad_fadt = table(b"FACP", &[0; 4]);
+        bad_fadt[10] ^= 0xff;
+        let fadt = table(b"FACP", &[1; 4]);
+        let madt_address = start + 36 + 3 * entry_size;
+        let addresses = [
+            madt_address,
+            madt_address + madt.len(),
+            madt_address + madt.len() + bad_fadt.len(),
+        ];
+        let mut entries = Vec::new();
+        for &address in &addresses {
+            entries.extend(&(address as u64).to_ne_bytes()[..entry_size]);
+        }
+        let mut memory = std::vec![0; start];
+        memory.extend(table(root_signature, &entries));
+        memory.extend(madt);
+        memory.extend(bad_fadt);
+        memory.extend(fadt);
+        memory
+    }
+
+    /// Returns the negated sum of the given bytes of the RSDP of the tag.
+    fn checksum<T>(tag: &T, length: usize) -> u8 {
+        let bytes =
+            unsafe { core::slice::from_raw_parts(tag as *const T as *const u8, 8 + length) };
+        bytes[8..]
+            .iter()
+            .fold(0u8, |acc, val| acc.wrapping_sub(*val))
+    }
+
+    /// Returns a RSDPv1 tag with a valid checksum.
+    fn rsdp_v1(rsdt_address: u32) -> RsdpV1Tag {
+        let new = |sum| RsdpV1Tag::new(*b"RSD PTR ", sum, *b"OEMID ", 0, rsdt_address);
+        new(checksum(&new(0), 20))
+    }
+
+    /// Returns a RSDPv2 tag with valid checksums.
+    fn rsdp_v2(rsdt_address: u32, xsdt_address: u64) -> RsdpV2Tag {
+        let new = |sum, ext_sum| {
+            let oem_id = *b"OEMID ";
+            RsdpV2Tag::new(
+                *b"RSD PTR ",
+                sum,
+                oem_id,
+                2,
+                rsdt_address,
+                36,
+                xsdt_address,
+                ext_sum,
+            )
+        };
+        let v1_checksum = checksum(&new(0, 0), 20);
+        new(v1_checksum, checksum(&new(v1_checksum, 0), 36))
+    }
+
+    /// Returns the boot information bytes with the given RSDP tag.
+    fn mbi<T>(tag: &T) -> Mbi {
+        let size = core::mem::size_of::<T>();
+        let tag = unsafe { core::slice::from_raw_parts(tag as *const T as *const u8, size) };
+        let end = 8 + ((size + 7) & !7);
+        let mut bytes = Mbi([0; 64]);
+        bytes.0[0..4].copy_from_slice(&(end as u32 + 8).to_ne_bytes());
+        bytes.0[8..8 + size].copy_from_slice(tag);
+        bytes.0[end..end + 4].copy_from_slice(&u32::from(TagType::End).to_ne_bytes());
+        bytes.0[end + 4..end + 8].copy_from_slice(&8_u32.to_ne_bytes());
+        bytes
+    }
+
+    fn check_tables(bi: &BootInformation, memory: &[u8], root_signature: [u8; 4]) {
+        let base = memory.as_ptr() as usize;
+        let tables = unsafe { AcpiTables::new(bi, |phys| base + phys as usize) }.unwrap();
+        assert_eq!(tables.root().signature(), root_signature);
+        assert_eq!(tables.headers().len(), 3);
+        let signatures: Vec<[u8; 4]> = tables.headers().map(|header| header.signature()).collect();
+        assert_eq!(signatures, [*b"APIC", *b"FACP", *b"FACP"]);
+        let madt = tables.madt().unwrap();
+        assert_eq!(madt.length(), 44);
+        assert_eq!(madt.revision(), 1);
+        assert_eq!(madt.oem_id(), *b"OEMID ");
+        assert_eq!(madt.oem_table_id(), *b"OEMTABLE");
+        assert_eq!(
+            tables.find(MADT_SIGNATURE).unwrap().bytes().unwrap().len(),
+            44
+        );
+        // the first FADT has an invalid checksum
+        assert_eq!(&tables.fadt().unwrap().bytes().unwrap()[36..], &[1; 4]);
+        assert!(tables.hpet().is_none());
+        assert!(tables.mcfg().is_none());
+    }
+
+    #[test]
+    fn test_rsdp_v1() {
+        let memory = physical_memory(b"RSDT", 4, 0x10);
+        let bytes = mbi(&rsdp_v1(0x10));
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        check_tables(&bi, &memory, *b"RSDT");
+
+        // the length of the MADT is smaller than its header
+        let mut memory = memory;
+        let madt = 0x10 + 36 + 3 * 4;
+        memory[madt + 4..madt + 8].copy_from_slice(&8_u32.to_ne_bytes());
+        let base = memory.as_ptr() as usize;
+        let tables = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) }.unwrap();
+        let header = tables.headers().next().unwrap();
+        assert_eq!(header.signature(), MADT_SIGNATURE);
+        assert!(header.bytes().is_none());
+        assert!(!header.checksum_is_valid());
+        assert!(tables.madt().is_none());
+    }
+
+    #[test]
+    fn test_rsdp_v2() {
+        let memory = physical_memory(b"XSDT", 8, 0x10);
+        let rsdp = rsdp_v2(0x1000, 0x10);
+        let bytes = mbi(&rsdp);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        check_tables(&bi, &memory, *b"XSDT");
+
+        // without an XSDT, the RSDT is used
+        let memory = physical_memory(b"RSDT", 4, 0x10);
+        let rsdp = rsdp_v2(0x10, 0);
+        let bytes = mbi(&rsdp);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        check_tables(&bi, &memory, *b"RSDT");
+    }
+
+    #[test]
+    fn test_invalid_root() {
+        let mut memory = physical_memory(b"RSDT", 4, 0);
+        let bytes = mbi(&rsdp_v1(0));
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        memory[20] ^= 0xff;
+        let base = memory.as_ptr() as usize;
+        let result = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) };
+        assert_eq!(result.unwrap_err(), AcpiError::InvalidChecksum);
+
+        memory[0] = b'X';
+        let result = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) };
+        assert_eq!(result.unwrap_err(), AcpiError::InvalidSignature(*b"XSDT"));
+
+        memory[0] = b'R';
+        memory[4..8].copy_from_slice(&8_u32.to_ne_bytes());
+        let result = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) };
+        assert_eq!(result.unwrap_err(), AcpiError::InvalidLength(8));
+
+        let mut bytes = Mbi([0; 64]);
+        bytes.0[0] = 16;
+        bytes.0[12] = 8;
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let result = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) };
+        assert_eq!(result.unwrap_err(), AcpiError::NoRsdp);
+
+        let bytes = mbi(&RsdpV1Tag::new(*b"RSD PTR ", 0, *b"OEMID ", 0, 0));
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let result = unsafe { AcpiTables::new(&bi, |phys| base + phys as usize) };
+        assert_eq!(
+            result.unwrap_err(),
+            AcpiError::InvalidRsdp(RsdpError::InvalidChecksum)
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{TagTrait, TagType, TagTypeId};
+use core::mem::size_of;
+
+/// The Advanced Power Management (APM) table tag. It contains the information
+/// of the APM BIOS installation check and the protected mode interface
+/// connect calls. See the Advanced Power Management (APM) BIOS Interface
+/// Specification, version 1.2, for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct ApmTag {
+    typ: TagTypeId,
+    size: u32,
+    version: u16,
+    cseg: u16,
+    offset: u32,
+    cseg_16: u16,
+    dseg: u16,
+    flags: u16,
+    cseg_len: u16,
+    cseg_16_len: u16,
+    dseg_len: u16,
+}
+
+unsafe impl TagTrait for ApmTag {
+    const ID: TagType = TagType::Apm;
+}
+
+impl ApmTag {
+    /// Creates a new tag.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        version: u16,
+        cseg: u16,
+        offset: u32,
+        cseg_16: u16,
+        dseg: u16,
+        flags: ApmFlags,
+        cseg_len: u16,
+        cseg_16_len: u16,
+        dseg_len: u16,
+    ) -> Self {
+        Self {
+            typ: TagType::Apm.into(),
+            size: size_of::<Self>() as u32,
+            version,
+            cseg,
+            offset,
+            cseg_16,
+            dseg,
+            flags: flags.bits(),
+            cseg_len,
+            cseg_16_len,
+            dseg_len,
+        }
+    }
+
+    /// Returns the APM version in BCD format, e.g. `0x0102` for version 1.2.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the 32-bit protected mode code segment base address.
+    pub fn cseg(&self) -> u16 {
+        self.cseg
+    }
+
+    /// Returns the offset of the entry point into the 32-bit protected mode
+    /// code segment.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the 16-bit protected mode code segment base address.
+    pub fn cseg_16(&self) -> u16 {
+        self.cseg_16
+    }
+
+    /// Returns the 16-bit protected mode data segment base address.
+    pub fn dseg(&self) -> u16 {
+        self.dseg
+    }
+
+    /// Returns the decoded flags of the APM BIOS. Unknown bits are dropped.
+    pub fn flags(&self) -> ApmFlags {
+        ApmFlags::from_bits_truncate(self.flags)
+    }
+
+    /// Returns the raw flags of the APM BIOS.
+    pub fn flags_raw(&self) -> u16 {
+        self.flags
+    }
+
+    /// Returns the length of the 32-bit protected mode code segment.
+    pub fn cseg_len(&self) -> u16 {
+        self.cseg_len
+    }
+
+    /// Returns the length of the 16-bit protected mode code segment.
+    pub fn cseg_16_len(&self) -> u16 {
+        self.cseg_16_len
+    }
+
+    /// Returns the length of the protected mode data segment.
+    pub fn dseg_len(&self) -> u16 {
+        self.dseg_len
+    }
+}
+
+bitflags! {
+    /// The flags that the APM BIOS reports in its installation check.
+    pub struct ApmFlags: u16 {
+        /// The 16-bit protected mode interface is supported.
+        const PROTECTED_MODE_16_BIT = 0x1;
+
+        /// The 32-bit protected mode interface is supported.
+        const PROTECTED_MODE_32_BIT = 0x2;
+
+        /// The CPU Idle call reduces the processor speed.
+        const CPU_IDLE_SLOWS_CLOCK = 0x4;
+
+        /// The BIOS power management is disabled.
+        const BIOS_PM_DISABLED = 0x8;
+
+        /// The BIOS power management is disengaged.
+        const BIOS_PM_DISENGAGED = 0x10;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApmFlags, ApmTag};
+    use crate::TagType;
+
+    /// Returns the tag structure in bytes in native endian format.
+    fn get_bytes() -> std::vec::Vec<u8> {
+        let fields: [u16; 2] = [0x0102, 0xf000];
+        let offset: u32 = 0xa9b0;
+        let more_fields: [u16; 6] = [0xf000, 0xf000, 0x3, 0xffff, 0xffff, 0xffff];
+        let mut bytes = std::vec::Vec::new();
+        bytes.extend(&u32::from(TagType::Apm).to_ne_bytes());
+        bytes.extend(&28_u32.to_ne_bytes());
+        fields
+            .iter()
+            .for_each(|field| bytes.extend(&field.to_ne_bytes()));
+        bytes.extend(&offset.to_ne_bytes());
+        more_fields
+            .iter()
+            .for_each(|field| bytes.extend(&field.to_ne_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn test_parse() {
+        let tag = get_bytes();
+        assert_eq!(tag.len(), core::mem::size_of::<ApmTag>());
+        let tag = unsafe { tag.as_ptr().cast::<ApmTag>().as_ref().unwrap() };
+        assert_eq!({ tag.typ }, TagType::Apm);
+        assert_eq!(tag.version(), 0x0102);
+        assert_eq!(tag.cseg(), 0xf000);
+        assert_eq!(tag.offset(), 0xa9b0);
+        assert_eq!(tag.cseg_16(), 0xf000);
+        assert_eq!(tag.dseg(), 0xf000);
+        assert_eq!(
+            tag.flags(),
+            ApmFlags::PROTECTED_MODE_16_BIT | ApmFlags::PROTECTED_MODE_32_BIT
+        );
+        assert_eq!(tag.cseg_len(), 0xffff);
+        assert_eq!(tag.cseg_16_len(), 0xffff);
+        assert_eq!(tag.dseg_len(), 0xffff);
+        assert_eq!(
+            *tag,
+            ApmTag::new(
+                0x0102,
+                0xf000,
+                0xa9b0,
+                0xf000,
+                0xf000,
+                ApmFlags::PROTECTED_MODE_16_BIT | ApmFlags::PROTECTED_MODE_32_BIT,
+                0xffff,
+                0xffff,
+                0xffff
+            )
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::TagType;
+use crate::{TagTrait, TagType, TagTypeId};
 use core::str::Utf8Error;
 
 /// This tag contains the name of the bootloader that is booting the kernel.
@@ -8,12 +8,16 @@ use core::str::Utf8Error;
 #[derive(Clone, Copy, Debug)]
 #[repr(C, packed)] // only repr(C) would add unwanted padding before first_section
 pub struct BootLoaderNameTag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     /// Null-terminated UTF-8 string
     string: u8,
 }
 
+unsafe impl TagTrait for BootLoaderNameTag {
+    const ID: TagType = TagType::BootLoaderName;
+}
+
 impl BootLoaderNameTag {
     /// Read the name of the bootloader that is booting the kernel.
     /// This is an null-terminated UTF-8 string. If this returns `Err` then perhaps the memory
@@ -47,7 +51,7 @@ mod tests {
         // size is: 4 bytes for tag + 4 bytes for size + length of null-terminated string
         let size = (4 + 4 + MSG.as_bytes().len() + 1) as u32;
         [
-            &((TagType::BootLoaderName as u32).to_ne_bytes()),
+            &(u32::from(TagType::BootLoaderName).to_ne_bytes()),
             &size.to_ne_bytes(),
             MSG.as_bytes(),
             // Null Byte
@@ -0,0 +1,112 @@
+use crate::{TagTrait, TagType, TagTypeId};
+use core::mem::size_of;
+
+/// The value of [`BootdevTag::partition`] and [`BootdevTag::sub_partition`] in
+/// the tag, if the (sub-)partition is unused.
+const UNUSED: u32 = 0xFFFF_FFFF;
+
+/// This tag indicates which BIOS disk device the boot loader loaded the OS
+/// image from. If the OS image was not loaded from a BIOS disk, then this tag
+/// must not be present. The operating system may use this field as a hint for
+/// determining its own root device, but is not required to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct BootdevTag {
+    typ: TagTypeId,
+    size: u32,
+    biosdev: u32,
+    slice: u32,
+    part: u32,
+}
+
+unsafe impl TagTrait for BootdevTag {
+    const ID: TagType = TagType::Bootdev;
+}
+
+impl BootdevTag {
+    /// Creates a new tag. `None` marks the partition or the sub-partition as
+    /// unused.
+    pub fn new(biosdev: u32, partition: Option<u32>, sub_partition: Option<u32>) -> Self {
+        Self {
+            typ: TagType::Bootdev.into(),
+            size: size_of::<Self>() as u32,
+            biosdev,
+            slice: partition.unwrap_or(UNUSED),
+            part: sub_partition.unwrap_or(UNUSED),
+        }
+    }
+
+    /// Returns the BIOS drive number as understood by the BIOS INT 0x13
+    /// low-level disk interface, e.g. 0x00 for the first floppy disk or 0x80
+    /// for the first hard disk.
+    pub fn biosdev(&self) -> u32 {
+        self.biosdev
+    }
+
+    /// Returns the number of the top-level partition, starting from 0, or
+    /// `None`, if the boot device is not partitioned.
+    pub fn partition(&self) -> Option<u32> {
+        Some(self.slice).filter(|&slice| slice != UNUSED)
+    }
+
+    /// Returns the number of the sub-partition within the top-level partition,
+    /// e.g. a BSD disklabel partition, or `None`, if it is unused.
+    pub fn sub_partition(&self) -> Option<u32> {
+        Some(self.part).filter(|&part| part != UNUSED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BootdevTag;
+    use crate::TagType;
+
+    /// Returns the tag structure in bytes in native endian format, as GRUB
+    /// creates it.
+    fn get_bytes(biosdev: u32, slice: u32, part: u32) -> std::vec::Vec<u8> {
+        [
+            &(u32::from(TagType::Bootdev).to_ne_bytes()),
+            &20_u32.to_ne_bytes(),
+            &biosdev.to_ne_bytes(),
+            &slice.to_ne_bytes(),
+            &part.to_ne_bytes(),
+        ]
+        .iter()
+        .flat_map(|bytes| bytes.iter())
+        .copied()
+        .collect()
+    }
+
+    /// Tests a hard disk without partitions.
+    #[test]
+    fn test_unpartitioned() {
+        let tag = get_bytes(0xe0, 0xffffffff, 0xffffffff);
+        let tag = unsafe { tag.as_ptr().cast::<BootdevTag>().as_ref().unwrap() };
+        assert_eq!({ tag.typ }, TagType::Bootdev);
+        assert_eq!(tag.biosdev(), 0xe0);
+        assert_eq!(tag.partition(), None);
+        assert_eq!(tag.sub_partition(), None);
+        assert_eq!(*tag, BootdevTag::new(0xe0, None, None));
+    }
+
+    /// Tests the second partition of the first hard disk (`(hd0,msdos2)`).
+    #[test]
+    fn test_partition() {
+        let tag = get_bytes(0x80, 1, 0xffffffff);
+        let tag = unsafe { tag.as_ptr().cast::<BootdevTag>().as_ref().unwrap() };
+        assert_eq!(tag.biosdev(), 0x80);
+        assert_eq!(tag.partition(), Some(1));
+        assert_eq!(tag.sub_partition(), None);
+        assert_eq!(*tag, BootdevTag::new(0x80, Some(1), None));
+    }
+
+    /// Tests a BSD disklabel partition within the first partition (`(hd0,1,a)`).
+    #[test]
+    fn test_sub_partition() {
+        let tag = get_bytes(0x80, 0, 0);
+        let tag = unsafe { tag.as_ptr().cast::<BootdevTag>().as_ref().unwrap() };
+        assert_eq!(tag.biosdev(), 0x80);
+        assert_eq!(tag.partition(), Some(0));
+        assert_eq!(tag.sub_partition(), Some(0));
+    }
+}
@@ -0,0 +1,749 @@
+//! Exports items [`BootInformationBuilder`] and [`BootInformationBytes`].
+
+use crate::builder::traits::StructAsBytes;
+use crate::{
+    ApmTag, BasicMemoryInfoTag, BootdevTag, EFIImageHandle32, EFIImageHandle64, EFIMemoryDesc,
+    EFISdt32, EFISdt64, ElfSectionFlags, ElfSectionType, FramebufferTag, FramebufferType,
+    ImageLoadPhysAddr, MemoryArea, RsdpV1Tag, RsdpV2Tag, TagType, VBEInfoTag,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::ops::Deref;
+
+/// Size of the fixed part of the Multiboot2 information structure,
+/// i.e. `total_size` and `reserved`.
+const BASIC_HEADER_SIZE: usize = 8;
+
+/// Size of the `typ` and `size` fields, that are common to all tags.
+const TAG_HEADER_SIZE: usize = 8;
+
+/// The fields of an ELF-64 section header, as passed to
+/// [`BootInformationBuilder::elf_sections_tag`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElfSectionHeader {
+    /// The offset of the name of the section in the section name string table.
+    pub name_index: u32,
+    /// The type of the section.
+    pub typ: ElfSectionType,
+    /// The flags of the section.
+    pub flags: ElfSectionFlags,
+    /// The (physical or virtual) address of the section in memory.
+    pub addr: u64,
+    /// The offset of the section in the ELF file.
+    pub offset: u64,
+    /// The size of the section in bytes.
+    pub size: u64,
+    /// The index of an associated section, e.g. the string table of a symbol table.
+    pub link: u32,
+    /// Extra information, whose meaning depends on the type of the section.
+    pub info: u32,
+    /// The alignment of the section.
+    pub addralign: u64,
+    /// The size of an entry, if the section holds a table of fixed-size entries.
+    pub entry_size: u64,
+}
+
+impl ElfSectionHeader {
+    /// Size of an ELF-64 section header.
+    const SIZE: usize = 64;
+
+    /// Returns the bytes of the ELF-64 section header in native endian format.
+    fn bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend(&self.name_index.to_ne_bytes());
+        bytes.extend(&u32::from(self.typ).to_ne_bytes());
+        bytes.extend(&self.flags.bits().to_ne_bytes());
+        bytes.extend(&self.addr.to_ne_bytes());
+        bytes.extend(&self.offset.to_ne_bytes());
+        bytes.extend(&self.size.to_ne_bytes());
+        bytes.extend(&self.link.to_ne_bytes());
+        bytes.extend(&self.info.to_ne_bytes());
+        bytes.extend(&self.addralign.to_ne_bytes());
+        bytes.extend(&self.entry_size.to_ne_bytes());
+        bytes
+    }
+}
+
+/// Builder to construct a valid Multiboot2 information structure (MBI) dynamically at
+/// runtime, for example in a bootloader or in a test harness of a kernel. The tags will
+/// appear in the order of their corresponding [`TagType`], except for the END tag.
+///
+/// The bytes of the MBI are produced by [`BootInformationBuilder::build`] and can be
+/// loaded with [`crate::load`] afterwards.
+#[derive(Debug, Default)]
+pub struct BootInformationBuilder {
+    // TagType::Cmdline
+    command_line_tag: Option<Vec<u8>>,
+    // TagType::BootLoaderName
+    boot_loader_name_tag: Option<Vec<u8>>,
+    // TagType::Module (multiple)
+    module_tags: Vec<Vec<u8>>,
+    // TagType::BasicMeminfo
+    basic_memory_info_tag: Option<BasicMemoryInfoTag>,
+    // TagType::Bootdev
+    bootdev_tag: Option<BootdevTag>,
+    // TagType::Mmap
+    memory_map_tag: Option<Vec<u8>>,
+    // TagType::Vbe
+    vbe_info_tag: Option<VBEInfoTag>,
+    // TagType::Framebuffer
+    framebuffer_tag: Option<Vec<u8>>,
+    // TagType::ElfSections
+    elf_sections_tag: Option<Vec<u8>>,
+    // TagType::Apm
+    apm_tag: Option<ApmTag>,
+    // TagType::Efi32
+    efi_sdt_32_tag: Option<EFISdt32>,
+    // TagType::Efi64
+    efi_sdt_64_tag: Option<EFISdt64>,
+    // TagType::Smbios
+    smbios_tag: Option<Vec<u8>>,
+    // TagType::AcpiV1
+    rsdp_v1_tag: Option<RsdpV1Tag>,
+    // TagType::AcpiV2
+    rsdp_v2_tag: Option<RsdpV2Tag>,
+    // TagType::Network (multiple)
+    network_tags: Vec<Vec<u8>>,
+    // TagType::EfiMmap
+    efi_memory_map_tag: Option<Vec<u8>>,
+    // TagType::EfiBs
+    efi_boot_services_not_exited: bool,
+    // TagType::Efi32Ih
+    efi_32_ih: Option<EFIImageHandle32>,
+    // TagType::Efi64Ih
+    efi_64_ih: Option<EFIImageHandle64>,
+    // TagType::LoadBaseAddr
+    load_base_addr: Option<ImageLoadPhysAddr>,
+}
+
+impl BootInformationBuilder {
+    /// New builder without any tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the size, if the value is a multiple of 8 or returns
+    /// the next number that is a multiple of 8. With this, one can
+    /// easily calculate the size of a Multiboot2 information structure,
+    /// where all the tags are 8-byte aligned.
+    const fn size_or_up_aligned(size: usize) -> usize {
+        let remainder = size % 8;
+        if remainder == 0 {
+            size
+        } else {
+            size + 8 - remainder
+        }
+    }
+
+    /// Creates the bytes of a tag with a dynamic size. The `size` property of the
+    /// tag is calculated from the length of `payload`.
+    fn dynamic_tag_bytes(typ: TagType, payload: &[u8]) -> Vec<u8> {
+        let size = (TAG_HEADER_SIZE + payload.len()) as u32;
+        let mut data = Vec::with_capacity(size as usize);
+        data.extend(&u32::from(typ).to_ne_bytes());
+        data.extend(&size.to_ne_bytes());
+        data.extend(payload);
+        data
+    }
+
+    /// Creates the bytes of a tag that holds a null-terminated UTF-8 string.
+    fn string_tag_bytes(typ: TagType, prefix: &[u8], string: &str) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(prefix.len() + string.len() + 1);
+        payload.extend(prefix);
+        payload.extend(string.as_bytes());
+        // null byte
+        payload.push(0);
+        Self::dynamic_tag_bytes(typ, &payload)
+    }
+
+    /// Adds the bytes of a tag to the final Multiboot2 information byte vector.
+    /// Zeroes are added, so that the next tag starts at an 8-byte aligned offset.
+    fn build_add_bytes(dest: &mut Vec<u8>, source: &[u8]) {
+        dest.extend(source);
+        let size = source.len();
+        let size_to_8_align_diff = Self::size_or_up_aligned(size) - size;
+        dest.extend([0].repeat(size_to_8_align_diff));
+    }
+
+    /// Constructs the bytes of a valid Multiboot2 information structure with the
+    /// given tags. The `total_size` and the end tag are filled in automatically.
+    pub fn build(self) -> BootInformationBytes {
+        // total_size and reserved; total_size is written at the end
+        let mut data = vec![0; BASIC_HEADER_SIZE];
+
+        if let Some(tag) = self.command_line_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.boot_loader_name_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        for tag in &self.module_tags {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.basic_memory_info_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.bootdev_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.memory_map_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.vbe_info_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.framebuffer_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.elf_sections_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.apm_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.efi_sdt_32_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.efi_sdt_64_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.smbios_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.rsdp_v1_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.rsdp_v2_tag.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        for tag in &self.network_tags {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if let Some(tag) = self.efi_memory_map_tag.as_ref() {
+            Self::build_add_bytes(&mut data, tag)
+        }
+        if self.efi_boot_services_not_exited {
+            Self::build_add_bytes(&mut data, &Self::dynamic_tag_bytes(TagType::EfiBs, &[]))
+        }
+        if let Some(tag) = self.efi_32_ih.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.efi_64_ih.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+        if let Some(tag) = self.load_base_addr.as_ref() {
+            Self::build_add_bytes(&mut data, &tag.struct_as_bytes())
+        }
+
+        Self::build_add_bytes(&mut data, &Self::dynamic_tag_bytes(TagType::End, &[]));
+
+        let total_size = data.len() as u32;
+        data[0..4].copy_from_slice(&total_size.to_ne_bytes());
+
+        BootInformationBytes::new(&data)
+    }
+
+    /// Adds a command line tag with the given command line string.
+    pub fn command_line_tag(mut self, command_line: &str) -> Self {
+        self.command_line_tag = Some(Self::string_tag_bytes(TagType::Cmdline, &[], command_line));
+        self
+    }
+
+    /// Adds a boot loader name tag with the given name.
+    pub fn boot_loader_name_tag(mut self, name: &str) -> Self {
+        self.boot_loader_name_tag =
+            Some(Self::string_tag_bytes(TagType::BootLoaderName, &[], name));
+        self
+    }
+
+    /// Adds a module tag for a module that is located at the physical addresses
+    /// `start..end`. Can be called multiple times to add multiple modules.
+    pub fn add_module_tag(mut self, start: u32, end: u32, cmdline: &str) -> Self {
+        let mut prefix = Vec::with_capacity(8);
+        prefix.extend(&start.to_ne_bytes());
+        prefix.extend(&end.to_ne_bytes());
+        self.module_tags
+            .push(Self::string_tag_bytes(TagType::Module, &prefix, cmdline));
+        self
+    }
+
+    /// Adds a basic memory info tag with the given amount of lower and upper memory in KiB.
+    pub fn basic_memory_info_tag(mut self, memory_lower: u32, memory_upper: u32) -> Self {
+        self.basic_memory_info_tag = Some(BasicMemoryInfoTag::new(memory_lower, memory_upper));
+        self
+    }
+
+    /// Adds a BIOS boot device tag.
+    pub fn bootdev_tag(mut self, tag: BootdevTag) -> Self {
+        self.bootdev_tag = Some(tag);
+        self
+    }
+
+    /// Adds a memory map tag with the given memory areas.
+    pub fn memory_map_tag(mut self, areas: &[MemoryArea]) -> Self {
+        let entry_size = size_of::<MemoryArea>() as u32;
+        let entry_version = 0_u32;
+        let mut payload = Vec::with_capacity(8 + areas.len() * entry_size as usize);
+        payload.extend(&entry_size.to_ne_bytes());
+        payload.extend(&entry_version.to_ne_bytes());
+        for area in areas {
+            payload.extend(area.struct_as_bytes());
+        }
+        self.memory_map_tag = Some(Self::dynamic_tag_bytes(TagType::Mmap, &payload));
+        self
+    }
+
+    /// Adds a VBE information tag.
+    pub fn vbe_info_tag(mut self, vbe_info_tag: VBEInfoTag) -> Self {
+        self.vbe_info_tag = Some(vbe_info_tag);
+        self
+    }
+
+    /// Adds a framebuffer tag with the properties of the given [`FramebufferTag`].
+    pub fn framebuffer_tag(mut self, framebuffer_tag: FramebufferTag) -> Self {
+        let mut payload = Vec::new();
+        payload.extend(&framebuffer_tag.address.to_ne_bytes());
+        payload.extend(&framebuffer_tag.pitch.to_ne_bytes());
+        payload.extend(&framebuffer_tag.width.to_ne_bytes());
+        payload.extend(&framebuffer_tag.height.to_ne_bytes());
+        payload.push(framebuffer_tag.bpp);
+        match framebuffer_tag.buffer_type {
+            FramebufferType::Indexed { palette } => {
+                payload.push(0);
+                // reserved
+                payload.extend(&0_u16.to_ne_bytes());
+                payload.extend(&(palette.len() as u32).to_ne_bytes());
+                for color in palette {
+                    payload.extend(&[color.red, color.green, color.blue]);
+                }
+            }
+            FramebufferType::RGB { red, green, blue } => {
+                payload.push(1);
+                // reserved
+                payload.extend(&0_u16.to_ne_bytes());
+                payload.extend(&[
+                    red.position,
+                    red.size,
+                    green.position,
+                    green.size,
+                    blue.position,
+                    blue.size,
+                ]);
+            }
+            FramebufferType::Text => {
+                payload.push(2);
+                // reserved
+                payload.extend(&0_u16.to_ne_bytes());
+            }
+        }
+        self.framebuffer_tag = Some(Self::dynamic_tag_bytes(TagType::Framebuffer, &payload));
+        self
+    }
+
+    /// Adds an ELF sections tag with the given ELF-64 section headers. `shndx` is
+    /// the index of the section that holds the section name string table.
+    pub fn elf_sections_tag(mut self, shndx: u32, sections: &[ElfSectionHeader]) -> Self {
+        let entry_size = ElfSectionHeader::SIZE as u32;
+        let mut payload = Vec::with_capacity(12 + sections.len() * ElfSectionHeader::SIZE);
+        payload.extend(&(sections.len() as u32).to_ne_bytes());
+        payload.extend(&entry_size.to_ne_bytes());
+        payload.extend(&shndx.to_ne_bytes());
+        for section in sections {
+            payload.extend(section.bytes());
+        }
+        self.elf_sections_tag = Some(Self::dynamic_tag_bytes(TagType::ElfSections, &payload));
+        self
+    }
+
+    /// Adds an APM table tag.
+    pub fn apm_tag(mut self, apm_tag: ApmTag) -> Self {
+        self.apm_tag = Some(apm_tag);
+        self
+    }
+
+    /// Adds an EFI 32-bit system table pointer tag.
+    pub fn efi_sdt_32_tag(mut self, efi_sdt_32_tag: EFISdt32) -> Self {
+        self.efi_sdt_32_tag = Some(efi_sdt_32_tag);
+        self
+    }
+
+    /// Adds an EFI 64-bit system table pointer tag.
+    pub fn efi_sdt_64_tag(mut self, efi_sdt_64_tag: EFISdt64) -> Self {
+        self.efi_sdt_64_tag = Some(efi_sdt_64_tag);
+        self
+    }
+
+    /// Adds an SMBIOS tag with the given version and the raw bytes of the SMBIOS tables.
+    pub fn smbios_tag(mut self, major: u8, minor: u8, tables: &[u8]) -> Self {
+        let mut payload = Vec::with_capacity(8 + tables.len());
+        payload.extend(&[major, minor, 0, 0, 0, 0, 0, 0]);
+        payload.extend(tables);
+        self.smbios_tag = Some(Self::dynamic_tag_bytes(TagType::Smbios, &payload));
+        self
+    }
+
+    /// Adds an (ACPI 1.0) RSDP tag.
+    pub fn rsdp_v1_tag(mut self, rsdp_v1_tag: RsdpV1Tag) -> Self {
+        self.rsdp_v1_tag = Some(rsdp_v1_tag);
+        self
+    }
+
+    /// Adds an (ACPI 2.0 or later) RSDP tag.
+    pub fn rsdp_v2_tag(mut self, rsdp_v2_tag: RsdpV2Tag) -> Self {
+        self.rsdp_v2_tag = Some(rsdp_v2_tag);
+        self
+    }
+
+    /// Adds a network tag with the given DHCP/BOOTP packet. Can be called multiple
+    /// times to add tags for multiple network cards.
+    pub fn add_network_tag(mut self, dhcp_packet: &[u8]) -> Self {
+        self.network_tags
+            .push(Self::dynamic_tag_bytes(TagType::Network, dhcp_packet));
+        self
+    }
+
+    /// Adds an EFI memory map tag with the given memory descriptors.
+    pub fn efi_memory_map_tag(mut self, descs: &[EFIMemoryDesc]) -> Self {
+        let desc_size = size_of::<EFIMemoryDesc>() as u32;
+        let desc_version = 1_u32;
+        let mut payload = Vec::with_capacity(8 + descs.len() * desc_size as usize);
+        payload.extend(&desc_size.to_ne_bytes());
+        payload.extend(&desc_version.to_ne_bytes());
+        for desc in descs {
+            payload.extend(desc.struct_as_bytes());
+        }
+        self.efi_memory_map_tag = Some(Self::dynamic_tag_bytes(TagType::EfiMmap, &payload));
+        self
+    }
+
+    /// Adds the tag that indicates that the EFI boot services were not exited.
+    pub fn efi_boot_services_not_exited_tag(mut self) -> Self {
+        self.efi_boot_services_not_exited = true;
+        self
+    }
+
+    /// Adds an EFI 32-bit image handle pointer tag.
+    pub fn efi_32_ih(mut self, efi_32_ih: EFIImageHandle32) -> Self {
+        self.efi_32_ih = Some(efi_32_ih);
+        self
+    }
+
+    /// Adds an EFI 64-bit image handle pointer tag.
+    pub fn efi_64_ih(mut self, efi_64_ih: EFIImageHandle64) -> Self {
+        self.efi_64_ih = Some(efi_64_ih);
+        self
+    }
+
+    /// Adds an image load base physical address tag.
+    pub fn load_base_addr(mut self, load_base_addr: ImageLoadPhysAddr) -> Self {
+        self.load_base_addr = Some(load_base_addr);
+        self
+    }
+}
+
+/// The bytes of a Multiboot2 information structure, as constructed by
+/// [`BootInformationBuilder::build`]. The bytes are located in an 8-byte aligned
+/// heap allocation, as the spec requires it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BootInformationBytes {
+    // u64 guarantees the 8-byte alignment
+    data: Vec<u64>,
+}
+
+impl BootInformationBytes {
+    fn new(bytes: &[u8]) -> Self {
+        debug_assert_eq!(bytes.len() % 8, 0, "the MBI size must be a multiple of 8");
+        let data = bytes
+            .chunks_exact(8)
+            .map(|chunk| {
+                let mut qword = [0; 8];
+                qword.copy_from_slice(chunk);
+                u64::from_ne_bytes(qword)
+            })
+            .collect();
+        Self { data }
+    }
+
+    /// Returns the bytes of the Multiboot2 information structure.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr().cast(), self.data.len() * 8) }
+    }
+}
+
+impl Deref for BootInformationBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Debug for BootInformationBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BootInformationBytes")
+            .field("ptr", &self.as_ptr())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::information::{BootInformationBuilder, ElfSectionHeader};
+    use crate::{
+        load, BootInformation, BootdevTag, EFIMemoryAreaType, EFIMemoryDesc, EFISdt64,
+        ElfSectionFlags, ElfSectionType, FramebufferColor, FramebufferField, FramebufferTag,
+        FramebufferType, ImageLoadPhysAddr, MemoryArea, MemoryAreaType, RsdpV1Tag, TagType,
+        VBEInfoTag,
+    };
+
+    /// Returns a VBE information tag with the given mode and otherwise zeroed fields.
+    fn vbe_info_tag(mode: u16) -> VBEInfoTag {
+        let mut bytes = [0_u8; core::mem::size_of::<VBEInfoTag>()];
+        bytes[0..4].copy_from_slice(&u32::from(TagType::Vbe).to_ne_bytes());
+        let size = bytes.len() as u32;
+        bytes[4..8].copy_from_slice(&size.to_ne_bytes());
+        bytes[8..10].copy_from_slice(&mode.to_ne_bytes());
+        unsafe { core::ptr::read_unaligned(bytes.as_ptr().cast()) }
+    }
+
+    /// Returns an ELF-64 section header with the given properties.
+    fn section(
+        name_index: u32,
+        typ: ElfSectionType,
+        flags: ElfSectionFlags,
+        addr: u64,
+        size: u64,
+    ) -> ElfSectionHeader {
+        ElfSectionHeader {
+            name_index,
+            typ,
+            flags,
+            addr,
+            offset: 0,
+            size,
+            link: 0,
+            info: 0,
+            addralign: 0x10,
+            entry_size: 0,
+        }
+    }
+
+    #[test]
+    fn test_size_or_up_aligned() {
+        assert_eq!(0, BootInformationBuilder::size_or_up_aligned(0));
+        assert_eq!(8, BootInformationBuilder::size_or_up_aligned(1));
+        assert_eq!(8, BootInformationBuilder::size_or_up_aligned(8));
+        assert_eq!(16, BootInformationBuilder::size_or_up_aligned(9));
+    }
+
+    #[test]
+    fn test_empty() {
+        let mbi = BootInformationBuilder::new().build();
+        assert_eq!(
+            mbi.as_bytes(),
+            &[
+                16, 0, 0, 0, // total_size
+                0, 0, 0, 0, // reserved
+                0, 0, 0, 0, // end tag type
+                8, 0, 0, 0, // end tag size
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder_round_trip() {
+        let palette = [
+            FramebufferColor {
+                red: 255,
+                green: 0,
+                blue: 0,
+            },
+            FramebufferColor {
+                red: 0,
+                green: 0,
+                blue: 255,
+            },
+        ];
+        let names = b"\0.text\0.shstrtab\0";
+        let sections = [
+            section(0, ElfSectionType::Unused, ElfSectionFlags::empty(), 0, 0),
+            section(
+                1,
+                ElfSectionType::ProgramSection,
+                ElfSectionFlags::ALLOCATED | ElfSectionFlags::EXECUTABLE,
+                0x100000,
+                0x1000,
+            ),
+            section(
+                7,
+                ElfSectionType::StringTable,
+                ElfSectionFlags::empty(),
+                names.as_ptr() as u64,
+                names.len() as u64,
+            ),
+        ];
+        let mbi = BootInformationBuilder::new()
+            .command_line_tag("--foo bar")
+            .boot_loader_name_tag("my bootloader")
+            .add_module_tag(0x1000, 0x2000, "initrd")
+            .add_module_tag(0x2000, 0x3000, "")
+            .basic_memory_info_tag(639, 129920)
+            .bootdev_tag(BootdevTag::new(0x80, Some(1), None))
+            .memory_map_tag(&[
+                MemoryArea::new(0, 0x9fc00, MemoryAreaType::Available),
+                MemoryArea::new(0x100000, 0x7ee0000, MemoryAreaType::Available),
+                MemoryArea::new(0xfffc0000, 0x40000, MemoryAreaType::Reserved),
+            ])
+            .vbe_info_tag(vbe_info_tag(0x117))
+            .framebuffer_tag(FramebufferTag {
+                address: 0xfd000000,
+                pitch: 5120,
+                width: 1280,
+                height: 720,
+                bpp: 8,
+                buffer_type: FramebufferType::Indexed { palette: &palette },
+            })
+            .elf_sections_tag(2, &sections)
+            .efi_sdt_64_tag(EFISdt64::new(0xdeadbeef))
+            .smbios_tag(2, 8, &[127, 4, 0xff, 0xff, 0, 0])
+            .rsdp_v1_tag(RsdpV1Tag::new(*b"RSD PTR ", 0, *b"BOCHS ", 0, 0x7fe1c00))
+            .add_network_tag(&[2; 236])
+            .add_network_tag(&[1; 3])
+            .efi_memory_map_tag(&[EFIMemoryDesc::new(7, 0x100000, 0, 4, 0)])
+            .load_base_addr(ImageLoadPhysAddr::new(0x200000))
+            .build();
+
+        assert_eq!(mbi.len() % 8, 0);
+        assert_eq!(mbi.as_ptr() as usize % 8, 0);
+
+        let bi = BootInformation::from_bytes(&mbi).unwrap();
+        assert_eq!(bi.total_size(), mbi.len());
+        assert_eq!(
+            bi.command_line_tag().unwrap().command_line(),
+            Ok("--foo bar")
+        );
+        assert_eq!(
+            bi.boot_loader_name_tag().unwrap().name(),
+            Ok("my bootloader")
+        );
+
+        let mut modules = bi.module_tags();
+        let module = modules.next().unwrap();
+        assert_eq!(module.start_address(), 0x1000);
+        assert_eq!(module.end_address(), 0x2000);
+        assert_eq!(module.cmdline(), Ok("initrd"));
+        assert_eq!(modules.next().unwrap().cmdline(), Ok(""));
+        assert!(modules.next().is_none());
+
+        let basic_memory_info = bi.basic_memory_info_tag().unwrap();
+        assert_eq!(basic_memory_info.memory_lower(), 639);
+        assert_eq!(basic_memory_info.memory_upper(), 129920);
+
+        let bootdev = bi.bootdev_tag().unwrap();
+        assert_eq!(bootdev.biosdev(), 0x80);
+        assert_eq!(bootdev.partition(), Some(1));
+        assert_eq!(bootdev.sub_partition(), None);
+
+        let areas = bi.memory_map_tag().unwrap().all_memory_areas();
+        assert_eq!(areas.count(), 3);
+        let mut areas = bi.memory_map_tag().unwrap().memory_areas();
+        assert_eq!(areas.next().unwrap().size(), 0x9fc00);
+        assert_eq!(areas.next().unwrap().start_address(), 0x100000);
+        assert!(areas.next().is_none());
+
+        assert_eq!({ bi.vbe_info_tag().unwrap().mode }, 0x117);
+
+        let framebuffer = bi.framebuffer_tag().unwrap().unwrap();
+        assert_eq!(framebuffer.address, 0xfd000000);
+        assert_eq!(framebuffer.bpp, 8);
+        assert_eq!(
+            framebuffer.buffer_type,
+            FramebufferType::Indexed { palette: &palette }
+        );
+
+        let elf_sections = bi.elf_sections_tag().unwrap().unwrap();
+        assert_eq!(elf_sections.all_sections().count(), 3);
+        // the unused section is skipped
+        assert_eq!(elf_sections.sections().count(), 2);
+        let text = elf_sections.find_by_name(".text").unwrap();
+        assert_eq!(text.index(), 1);
+        assert_eq!(text.section_type(), ElfSectionType::ProgramSection);
+        assert_eq!(
+            text.flags(),
+            ElfSectionFlags::ALLOCATED | ElfSectionFlags::EXECUTABLE
+        );
+        assert_eq!(text.start_address(), 0x100000);
+        assert_eq!(text.size(), 0x1000);
+        let shstrtab = elf_sections.section(2).unwrap();
+        assert_eq!(shstrtab.name(), Ok(".shstrtab"));
+        assert_eq!(shstrtab.section_type(), ElfSectionType::StringTable);
+
+        assert_eq!(bi.efi_sdt_64_tag().unwrap().sdt_address(), 0xdeadbeef);
+        let smbios = bi.smbios_tag().unwrap();
+        assert_eq!((smbios.major(), smbios.minor()), (2, 8));
+        assert_eq!(smbios.tables(), &[127, 4, 0xff, 0xff, 0, 0]);
+        assert_eq!(smbios.structures().next().unwrap().typ(), 127);
+        let rsdp = bi.rsdp_v1_tag().unwrap();
+        assert_eq!(rsdp.signature(), Ok("RSD PTR "));
+        assert_eq!(rsdp.oem_id(), Ok("BOCHS "));
+        assert_eq!(rsdp.rsdt_address(), 0x7fe1c00);
+
+        let mut network_tags = bi.network_tags();
+        assert_eq!(network_tags.next().unwrap().dhcp().unwrap().op(), 2);
+        assert_eq!(network_tags.next().unwrap().dhcp_packet(), &[1; 3]);
+        assert!(network_tags.next().is_none());
+
+        let desc = bi
+            .efi_memory_map_tag()
+            .unwrap()
+            .memory_areas()
+            .next()
+            .unwrap();
+        assert_eq!(desc.typ(), EFIMemoryAreaType::EfiConventionalMemory);
+        assert_eq!(desc.physical_address(), 0x100000);
+        assert_eq!(desc.size(), 4 * 4096);
+
+        assert_eq!(bi.load_base_addr().unwrap().load_base_addr(), 0x200000);
+    }
+
+    #[test]
+    fn test_builder_framebuffer_rgb() {
+        let rgb = FramebufferType::RGB {
+            red: FramebufferField {
+                position: 16,
+                size: 8,
+            },
+            green: FramebufferField {
+                position: 8,
+                size: 8,
+            },
+            blue: FramebufferField {
+                position: 0,
+                size: 8,
+            },
+        };
+        let mbi = BootInformationBuilder::new()
+            .framebuffer_tag(FramebufferTag {
+                address: 4244635648,
+                pitch: 5120,
+                width: 1280,
+                height: 720,
+                bpp: 32,
+                buffer_type: rgb,
+            })
+            .build();
+        // must be equal to the bytes that GRUB2 produces, see test `framebuffer_tag_rgb`
+        assert_eq!(mbi.len(), 56);
+        let bi = unsafe { load(mbi.as_ptr() as usize) }.unwrap();
+        let framebuffer = bi.framebuffer_tag().unwrap().unwrap();
+        assert_eq!(framebuffer.width, 1280);
+        assert_eq!(framebuffer.height, 720);
+        assert!(matches!(
+            framebuffer.buffer_type,
+            FramebufferType::RGB { .. }
+        ));
+    }
+}
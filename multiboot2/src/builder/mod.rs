@@ -0,0 +1,6 @@
+//! Module for the builder-feature.
+
+mod information;
+mod traits;
+
+pub use information::{BootInformationBuilder, BootInformationBytes, ElfSectionHeader};
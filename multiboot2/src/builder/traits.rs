@@ -0,0 +1,64 @@
+//! Module for the helper trait [`StructAsBytes`].
+
+use crate::{
+    ApmTag, BasicMemoryInfoTag, BootdevTag, EFIImageHandle32, EFIImageHandle64, EFIMemoryDesc,
+    EFISdt32, EFISdt64, ImageLoadPhysAddr, MemoryArea, RsdpV1Tag, RsdpV2Tag, VBEInfoTag,
+};
+use core::mem::size_of;
+
+/// Trait for all tags that helps to create a byte array from the tag.
+/// Useful in builders to construct a byte vector that
+/// represents the Multiboot2 information with all its tags.
+pub(crate) trait StructAsBytes: Sized {
+    /// Returns the size in bytes of the struct, as known during compile
+    /// time. This doesn't use read the "size" field of tags.
+    fn byte_size(&self) -> usize {
+        size_of::<Self>()
+    }
+
+    /// Returns a byte pointer to the begin of the struct.
+    fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    /// Returns the structure as a vector of its bytes.
+    /// The length is determined by [`StructAsBytes::byte_size`].
+    fn struct_as_bytes(&self) -> alloc::vec::Vec<u8> {
+        let ptr = self.as_ptr();
+        let mut vec = alloc::vec::Vec::with_capacity(self.byte_size());
+        for i in 0..self.byte_size() {
+            vec.push(unsafe { *ptr.add(i) })
+        }
+        vec
+    }
+}
+
+impl StructAsBytes for ApmTag {}
+impl StructAsBytes for BasicMemoryInfoTag {}
+impl StructAsBytes for BootdevTag {}
+impl StructAsBytes for EFIImageHandle32 {}
+impl StructAsBytes for EFIImageHandle64 {}
+impl StructAsBytes for EFISdt32 {}
+impl StructAsBytes for EFISdt64 {}
+impl StructAsBytes for ImageLoadPhysAddr {}
+impl StructAsBytes for RsdpV1Tag {}
+impl StructAsBytes for RsdpV2Tag {}
+impl StructAsBytes for VBEInfoTag {}
+
+impl StructAsBytes for MemoryArea {}
+impl StructAsBytes for EFIMemoryDesc {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_bytes() {
+        let tag = ImageLoadPhysAddr::new(0x1337);
+        let bytes = tag.struct_as_bytes();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[0..4], &21_u32.to_ne_bytes());
+        assert_eq!(&bytes[4..8], &12_u32.to_ne_bytes());
+        assert_eq!(&bytes[8..12], &0x1337_u32.to_ne_bytes());
+    }
+}
@@ -1,17 +1,31 @@
 //! All MBI tags related to (U)EFI.
 
-use crate::TagType;
+use crate::{TagTrait, TagType, TagTypeId};
+use core::mem::size_of;
 
 /// EFI system table in 32 bit mode
 #[derive(Clone, Copy, Debug)]
 #[repr(C, packed)] // only repr(C) would add unwanted padding before first_section
 pub struct EFISdt32 {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     pointer: u32,
 }
 
+unsafe impl TagTrait for EFISdt32 {
+    const ID: TagType = TagType::Efi32;
+}
+
 impl EFISdt32 {
+    /// Creates a new tag that points to the EFI system table at the given physical address.
+    pub fn new(pointer: u32) -> Self {
+        Self {
+            typ: TagType::Efi32.into(),
+            size: size_of::<Self>() as u32,
+            pointer,
+        }
+    }
+
     /// The physical address of a i386 EFI system table.
     pub fn sdt_address(&self) -> usize {
         self.pointer as usize
@@ -22,12 +36,25 @@ impl EFISdt32 {
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
 pub struct EFISdt64 {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     pointer: u64,
 }
 
+unsafe impl TagTrait for EFISdt64 {
+    const ID: TagType = TagType::Efi64;
+}
+
 impl EFISdt64 {
+    /// Creates a new tag that points to the EFI system table at the given physical address.
+    pub fn new(pointer: u64) -> Self {
+        Self {
+            typ: TagType::Efi64.into(),
+            size: size_of::<Self>() as u32,
+            pointer,
+        }
+    }
+
     /// The physical address of a x86_64 EFI system table.
     pub fn sdt_address(&self) -> usize {
         self.pointer as usize
@@ -38,12 +65,25 @@ impl EFISdt64 {
 #[derive(Debug)]
 #[repr(C)]
 pub struct EFIImageHandle32 {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     pointer: u32,
 }
 
+unsafe impl TagTrait for EFIImageHandle32 {
+    const ID: TagType = TagType::Efi32Ih;
+}
+
 impl EFIImageHandle32 {
+    /// Creates a new tag that points to the EFI image handle at the given physical address.
+    pub fn new(pointer: u32) -> Self {
+        Self {
+            typ: TagType::Efi32Ih.into(),
+            size: size_of::<Self>() as u32,
+            pointer,
+        }
+    }
+
     /// Returns the physical address of the EFI image handle.
     pub fn image_handle(&self) -> usize {
         self.pointer as usize
@@ -54,12 +94,25 @@ impl EFIImageHandle32 {
 #[derive(Debug)]
 #[repr(C)]
 pub struct EFIImageHandle64 {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     pointer: u64,
 }
 
+unsafe impl TagTrait for EFIImageHandle64 {
+    const ID: TagType = TagType::Efi64Ih;
+}
+
 impl EFIImageHandle64 {
+    /// Creates a new tag that points to the EFI image handle at the given physical address.
+    pub fn new(pointer: u64) -> Self {
+        Self {
+            typ: TagType::Efi64Ih.into(),
+            size: size_of::<Self>() as u32,
+            pointer,
+        }
+    }
+
     /// Returns the physical address of the EFI image handle.
     pub fn image_handle(&self) -> usize {
         self.pointer as usize
@@ -1,5 +1,14 @@
+use crate::elf_symbols::SymbolTable;
 use crate::tag_type::Tag;
+use core::convert::TryFrom;
 use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::ops::Range;
+use core::slice;
+use core::str::Utf8Error;
+
+/// The section index that marks an undefined section reference (`SHN_UNDEF`).
+const SHN_UNDEF: u32 = 0;
 
 /// This tag contains section header table from an ELF kernel.
 ///
@@ -7,17 +16,68 @@ use core::fmt::{Debug, Formatter};
 #[derive(Debug)]
 pub struct ElfSectionsTag {
     inner: *const ElfSectionsTagInner,
+    class: ElfClass,
+    offset: usize,
+}
+
+/// Creates the [`ElfSectionsTag`] from a tag. Returns `None`, if the tag is
+/// too small for the fixed fields and an error, if the section headers are
+/// malformed.
+pub unsafe fn elf_sections_tag(
+    tag: &Tag,
     offset: usize,
+) -> Option<Result<ElfSectionsTag, ElfSectionsTagError>> {
+    if (tag.size as usize) < size_of::<Tag>() + size_of::<ElfSectionsTagInner>() {
+        return None;
+    }
+    let inner = (tag as *const Tag).offset(1) as *const ElfSectionsTagInner;
+    Some(ElfSectionsTag::new(inner, tag.size, offset))
 }
 
-pub unsafe fn elf_sections_tag(tag: &Tag, offset: usize) -> ElfSectionsTag {
-    assert_eq!(9, tag.typ);
-    let es = ElfSectionsTag {
-        inner: (tag as *const Tag).offset(1) as *const ElfSectionsTagInner,
-        offset,
-    };
-    assert!((es.get().entry_size * es.get().shndx) <= tag.size);
-    es
+/// Errors that can occur while parsing the [`ElfSectionsTag`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElfSectionsTagError {
+    /// The size of a section header is neither the one of 32-bit ELF (40 bytes)
+    /// nor the one of 64-bit ELF (64 bytes). Holds the size.
+    UnsupportedEntrySize(u32),
+    /// The section headers are not completely within the tag.
+    SectionsOutOfBounds,
+}
+
+/// Errors that can occur while reading the name of an [`ElfSection`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElfSectionNameError {
+    /// The index of the string table is `SHN_UNDEF` or not within the tag, or
+    /// the string table is not loaded, i.e. its address is 0.
+    MissingStringTable,
+    /// The name or its terminating null byte is not within the string table.
+    OutOfBounds,
+    /// The name is not valid UTF-8.
+    Utf8(Utf8Error),
+}
+
+/// The ELF class of the section headers, derived from their size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ElfClass {
+    Elf32,
+    Elf64,
+}
+
+impl ElfClass {
+    fn from_entry_size(entry_size: u32) -> Option<Self> {
+        match entry_size as usize {
+            x if x == size_of::<ElfSectionInner32>() => Some(ElfClass::Elf32),
+            x if x == size_of::<ElfSectionInner64>() => Some(ElfClass::Elf64),
+            _ => None,
+        }
+    }
+
+    fn entry_size(self) -> usize {
+        match self {
+            ElfClass::Elf32 => size_of::<ElfSectionInner32>(),
+            ElfClass::Elf64 => size_of::<ElfSectionInner64>(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -29,6 +89,32 @@ struct ElfSectionsTagInner {
 }
 
 impl ElfSectionsTag {
+    /// Creates the tag from the fixed fields of the tag, which are followed by
+    /// the section headers. Rejects unsupported sizes of the section headers and
+    /// section headers that exceed the `tag_size`.
+    unsafe fn new(
+        inner: *const ElfSectionsTagInner,
+        tag_size: u32,
+        offset: usize,
+    ) -> Result<Self, ElfSectionsTagError> {
+        let fields = *inner;
+        let class = ElfClass::from_entry_size(fields.entry_size)
+            .ok_or(ElfSectionsTagError::UnsupportedEntrySize(fields.entry_size))?;
+        let sections_size = (fields.number_of_sections as usize)
+            .checked_mul(class.entry_size())
+            .ok_or(ElfSectionsTagError::SectionsOutOfBounds)?;
+        let max_sections_size =
+            tag_size as usize - size_of::<Tag>() - size_of::<ElfSectionsTagInner>();
+        if sections_size > max_sections_size {
+            return Err(ElfSectionsTagError::SectionsOutOfBounds);
+        }
+        Ok(Self {
+            inner,
+            class,
+            offset,
+        })
+    }
+
     /// Get an iterator of loaded ELF sections.
     ///
     /// # Examples
@@ -43,16 +129,118 @@ impl ElfSectionsTag {
     /// }
     /// ```
     pub fn sections(&self) -> ElfSectionIter {
-        let string_section_offset = (self.get().shndx * self.get().entry_size) as isize;
-        let string_section_ptr =
-            unsafe { self.first_section().offset(string_section_offset) as *const _ };
+        self.iter(false)
+    }
+
+    /// Get an iterator of all section headers, including the ones of type
+    /// [`ElfSectionType::Unused`], such as the null section at index 0. The
+    /// [`ElfSection::index`] of the returned sections therefore matches their
+    /// position in the iteration, which allows to resolve section indices of
+    /// symbols or of [`ElfSection::link`].
+    pub fn all_sections(&self) -> ElfSectionIter {
+        self.iter(true)
+    }
+
+    /// Returns the section header with the given index, if it is within the
+    /// tag. Unlike [`Self::sections`], this also returns sections of type
+    /// [`ElfSectionType::Unused`].
+    pub fn section(&self, index: u32) -> Option<ElfSection> {
+        Some(ElfSection {
+            inner: self.section_header(index)?,
+            index,
+            string_section: self.string_section(),
+            class: self.class,
+            offset: self.offset,
+        })
+    }
+
+    fn iter(&self, include_unused: bool) -> ElfSectionIter {
         ElfSectionIter {
             current_section: self.first_section(),
-            remaining_sections: self.get().number_of_sections,
-            entry_size: self.get().entry_size,
-            string_section: string_section_ptr,
+            index: 0,
+            number_of_sections: self.get().number_of_sections,
+            class: self.class,
+            string_section: self.string_section(),
             offset: self.offset,
+            include_unused,
+        }
+    }
+
+    /// Returns the first section with the given name, e.g. `".text"`.
+    pub fn find_by_name(&self, name: &str) -> Option<ElfSection> {
+        self.sections().find(|section| section.name() == Ok(name))
+    }
+
+    /// Returns the allocated section whose address range contains `addr`.
+    /// Sections that don't occupy memory are not considered.
+    pub fn section_containing(&self, addr: u64) -> Option<ElfSection> {
+        self.sections().find(|section| {
+            section.is_allocated()
+                && section.start_address() <= addr
+                && addr < section.end_address()
+        })
+    }
+
+    /// Returns the range from the lowest start address to the highest end
+    /// address of all allocated, non-empty sections, i.e. the memory that the
+    /// kernel image occupies. Returns `None`, if there is no such section.
+    ///
+    /// The addresses are the ones of the section headers. For a higher-half
+    /// kernel, these are usually virtual addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let kernel = boot_info.elf_sections_tag().unwrap().unwrap().kernel_extent().unwrap();
+    /// frame_allocator.reserve(kernel.start, kernel.end);
+    /// ```
+    pub fn kernel_extent(&self) -> Option<Range<u64>> {
+        self.sections()
+            .filter(|section| section.is_allocated() && section.size() != 0)
+            .map(|section| section.start_address()..section.end_address())
+            .reduce(|extent, section| extent.start.min(section.start)..extent.end.max(section.end))
+    }
+
+    /// Returns the symbol table (`.symtab`) of the kernel together with its
+    /// linked string table, if both are present and valid.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let symbols = boot_info.elf_sections_tag().unwrap().unwrap().symbol_table().unwrap();
+    /// if let Some((name, offset)) = symbols.symbolize(return_address) {
+    ///     println!("{}+{:#x}", name, offset);
+    /// }
+    /// ```
+    pub fn symbol_table(&self) -> Option<SymbolTable<'_>> {
+        let symtab = self
+            .sections()
+            .find(|section| section.section_type() == ElfSectionType::LinkerSymbolTable)?;
+        let strtab = self.section(symtab.link())?;
+        if strtab.section_type() != ElfSectionType::StringTable {
+            return None;
         }
+        unsafe { SymbolTable::new(&symtab, &strtab, self.class) }
+    }
+
+    /// Returns the section header of the string table with the section names
+    /// or a null pointer, if the index is `SHN_UNDEF` or not within the tag.
+    fn string_section(&self) -> *const u8 {
+        match self.get().shndx {
+            SHN_UNDEF => core::ptr::null(),
+            shndx => self.section_header(shndx).unwrap_or(core::ptr::null()),
+        }
+    }
+
+    /// Returns the section header with the given index, if it is within the tag.
+    fn section_header(&self, index: u32) -> Option<*const u8> {
+        if index >= self.get().number_of_sections {
+            return None;
+        }
+        Some(unsafe {
+            self.first_section()
+                .add(index as usize * self.class.entry_size())
+        })
     }
 
     fn first_section(&self) -> *const u8 {
@@ -68,28 +256,31 @@ impl ElfSectionsTag {
 #[derive(Clone)]
 pub struct ElfSectionIter {
     current_section: *const u8,
-    remaining_sections: u32,
-    entry_size: u32,
+    index: u32,
+    number_of_sections: u32,
+    class: ElfClass,
     string_section: *const u8,
     offset: usize,
+    include_unused: bool,
 }
 
 impl Iterator for ElfSectionIter {
     type Item = ElfSection;
 
     fn next(&mut self) -> Option<ElfSection> {
-        while self.remaining_sections != 0 {
+        while self.index < self.number_of_sections {
             let section = ElfSection {
                 inner: self.current_section,
+                index: self.index,
                 string_section: self.string_section,
-                entry_size: self.entry_size,
+                class: self.class,
                 offset: self.offset,
             };
 
-            self.current_section = unsafe { self.current_section.offset(self.entry_size as isize) };
-            self.remaining_sections -= 1;
+            self.current_section = unsafe { self.current_section.add(self.class.entry_size()) };
+            self.index += 1;
 
-            if section.section_type() != ElfSectionType::Unused {
+            if self.include_unused || section.section_type() != ElfSectionType::Unused {
                 return Some(section);
             }
         }
@@ -111,10 +302,12 @@ impl Default for ElfSectionIter {
     fn default() -> Self {
         Self {
             current_section: core::ptr::null(),
-            remaining_sections: 0,
-            entry_size: 0,
+            index: 0,
+            number_of_sections: 0,
+            class: ElfClass::Elf64,
             string_section: core::ptr::null(),
             offset: 0,
+            include_unused: false,
         }
     }
 }
@@ -123,8 +316,9 @@ impl Default for ElfSectionIter {
 #[derive(Debug)]
 pub struct ElfSection {
     inner: *const u8,
+    index: u32,
     string_section: *const u8,
-    entry_size: u32,
+    class: ElfClass,
     offset: usize,
 }
 
@@ -159,25 +353,14 @@ struct ElfSectionInner64 {
 }
 
 impl ElfSection {
+    /// Get the index of the section header in the section header table.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
     /// Get the section type as a `ElfSectionType` enum variant.
     pub fn section_type(&self) -> ElfSectionType {
-        match self.get().typ() {
-            0 => ElfSectionType::Unused,
-            1 => ElfSectionType::ProgramSection,
-            2 => ElfSectionType::LinkerSymbolTable,
-            3 => ElfSectionType::StringTable,
-            4 => ElfSectionType::RelaRelocation,
-            5 => ElfSectionType::SymbolHashTable,
-            6 => ElfSectionType::DynamicLinkingTable,
-            7 => ElfSectionType::Note,
-            8 => ElfSectionType::Uninitialized,
-            9 => ElfSectionType::RelRelocation,
-            10 => ElfSectionType::Reserved,
-            11 => ElfSectionType::DynamicLoaderSymbolTable,
-            0x6000_0000..=0x6FFF_FFFF => ElfSectionType::EnvironmentSpecific,
-            0x7000_0000..=0x7FFF_FFFF => ElfSectionType::ProcessorSpecific,
-            _ => panic!(),
-        }
+        self.get().typ().into()
     }
 
     /// Get the "raw" section type as a `u32`
@@ -185,22 +368,13 @@ impl ElfSection {
         self.get().typ()
     }
 
-    /// Read the name of the section.
-    pub fn name(&self) -> &str {
-        use core::{slice, str};
-
-        let name_ptr = unsafe { self.string_table().offset(self.get().name_index() as isize) };
-
-        // strlen without null byte
-        let strlen = {
-            let mut len = 0;
-            while unsafe { *name_ptr.offset(len) } != 0 {
-                len += 1;
-            }
-            len as usize
-        };
-
-        str::from_utf8(unsafe { slice::from_raw_parts(name_ptr, strlen) }).unwrap()
+    /// Read the name of the section from the string table. The name is
+    /// bounded by the size of the string table.
+    pub fn name(&self) -> Result<&str, ElfSectionNameError> {
+        let string_table = unsafe { self.string_table() }?;
+        let name = c_str_at(string_table, self.get().name_index() as usize)
+            .ok_or(ElfSectionNameError::OutOfBounds)?;
+        core::str::from_utf8(name).map_err(ElfSectionNameError::Utf8)
     }
 
     /// Get the physical start address of the section.
@@ -210,9 +384,10 @@ impl ElfSection {
 
     /// Get the physical end address of the section.
     ///
-    /// This is the same as doing `section.start_address() + section.size()`
+    /// This is the same as doing `section.start_address() + section.size()`,
+    /// but saturates instead of overflowing.
     pub fn end_address(&self) -> u64 {
-        self.get().addr() + self.get().size()
+        self.get().addr().saturating_add(self.get().size())
     }
 
     /// Get the section's size in bytes.
@@ -230,6 +405,31 @@ impl ElfSection {
         self.get().addralign()
     }
 
+    /// Get the offset of the section in the ELF file.
+    pub fn offset(&self) -> u64 {
+        self.get().offset()
+    }
+
+    /// Get the section header table index link, whose interpretation depends
+    /// on the section type. For example, the link of a symbol table is the
+    /// index of the associated string table.
+    pub fn link(&self) -> u32 {
+        self.get().link()
+    }
+
+    /// Get the extra information of the section, whose interpretation depends
+    /// on the section type. For example, the info of a symbol table is the
+    /// index of the first non-local symbol.
+    pub fn info(&self) -> u32 {
+        self.get().info()
+    }
+
+    /// Get the size of each entry in bytes, if the section holds a table of
+    /// fixed-size entries, such as a symbol table. Otherwise, this is 0.
+    pub fn entry_size(&self) -> u64 {
+        self.get().entry_size()
+    }
+
     /// Get the section's flags.
     pub fn flags(&self) -> ElfSectionFlags {
         ElfSectionFlags::from_bits_truncate(self.get().flags())
@@ -240,24 +440,71 @@ impl ElfSection {
         self.flags().contains(ElfSectionFlags::ALLOCATED)
     }
 
+    /// Returns the content of the section in memory, i.e. at the (offset
+    /// adjusted) start address. Returns `None`, if the section is not loaded,
+    /// i.e. its address is 0, or if the size doesn't fit into the address
+    /// space.
+    ///
+    /// # Safety
+    /// The section must be loaded into memory by the bootloader.
+    pub(crate) unsafe fn loaded_bytes<'a>(&self) -> Option<&'a [u8]> {
+        loaded_bytes(self.get(), self.offset)
+    }
+
     fn get(&self) -> &dyn ElfSectionInner {
-        match self.entry_size {
-            40 => unsafe { &*(self.inner as *const ElfSectionInner32) },
-            64 => unsafe { &*(self.inner as *const ElfSectionInner64) },
-            _ => panic!(),
+        unsafe { section_header(self.inner, self.class) }
+    }
+
+    /// Returns the (offset adjusted) content of the string table with the
+    /// section names.
+    unsafe fn string_table<'a>(&self) -> Result<&'a [u8], ElfSectionNameError> {
+        if self.string_section.is_null() {
+            return Err(ElfSectionNameError::MissingStringTable);
+        }
+        let string_section = section_header(self.string_section, self.class);
+        if string_section.addr() == 0 {
+            return Err(ElfSectionNameError::MissingStringTable);
         }
+        loaded_bytes(string_section, self.offset).ok_or(ElfSectionNameError::OutOfBounds)
     }
+}
 
-    unsafe fn string_table(&self) -> *const u8 {
-        let addr = match self.entry_size {
-            40 => (*(self.string_section as *const ElfSectionInner32)).addr as usize,
-            64 => (*(self.string_section as *const ElfSectionInner64)).addr as usize,
-            _ => panic!(),
-        };
-        (addr + self.offset) as *const _
+/// Interprets the section header at `ptr` according to the ELF class.
+unsafe fn section_header<'a>(ptr: *const u8, class: ElfClass) -> &'a dyn ElfSectionInner {
+    match class {
+        ElfClass::Elf32 => &*(ptr as *const ElfSectionInner32),
+        ElfClass::Elf64 => &*(ptr as *const ElfSectionInner64),
     }
 }
 
+/// Returns the content of a section in memory at its start address plus
+/// `offset`. Returns `None`, if the section is not loaded or if the size
+/// doesn't fit into the address space.
+unsafe fn loaded_bytes<'a>(header: &dyn ElfSectionInner, offset: usize) -> Option<&'a [u8]> {
+    let addr = usize::try_from(header.addr()).ok()?;
+    let size = usize::try_from(header.size()).ok()?;
+    // sections that the bootloader didn't load, such as `.symtab` and
+    // `.strtab` in many cases, have the address 0
+    if addr == 0 {
+        return None;
+    }
+    let addr = addr.wrapping_add(offset);
+    if addr == 0 {
+        return None;
+    }
+    addr.checked_add(size)?;
+    Some(slice::from_raw_parts(addr as *const u8, size))
+}
+
+/// Returns the null-terminated string that starts at `index` in the string
+/// table `strings`, without the null byte. Returns `None`, if the index or the
+/// terminating null byte is not within the string table.
+pub(crate) fn c_str_at(strings: &[u8], index: usize) -> Option<&[u8]> {
+    let bytes = strings.get(index..)?;
+    let len = bytes.iter().position(|&byte| byte == 0)?;
+    Some(&bytes[..len])
+}
+
 trait ElfSectionInner {
     fn name_index(&self) -> u32;
 
@@ -267,9 +514,17 @@ trait ElfSectionInner {
 
     fn addr(&self) -> u64;
 
+    fn offset(&self) -> u64;
+
     fn size(&self) -> u64;
 
+    fn link(&self) -> u32;
+
+    fn info(&self) -> u32;
+
     fn addralign(&self) -> u64;
+
+    fn entry_size(&self) -> u64;
 }
 
 impl ElfSectionInner for ElfSectionInner32 {
@@ -289,13 +544,29 @@ impl ElfSectionInner for ElfSectionInner32 {
         self.addr.into()
     }
 
+    fn offset(&self) -> u64 {
+        self.offset.into()
+    }
+
     fn size(&self) -> u64 {
         self.size.into()
     }
 
+    fn link(&self) -> u32 {
+        self.link
+    }
+
+    fn info(&self) -> u32 {
+        self.info
+    }
+
     fn addralign(&self) -> u64 {
         self.addralign.into()
     }
+
+    fn entry_size(&self) -> u64 {
+        self.entry_size.into()
+    }
 }
 
 impl ElfSectionInner for ElfSectionInner64 {
@@ -315,71 +586,212 @@ impl ElfSectionInner for ElfSectionInner64 {
         self.addr
     }
 
+    fn offset(&self) -> u64 {
+        self.offset
+    }
+
     fn size(&self) -> u64 {
         self.size
     }
 
+    fn link(&self) -> u32 {
+        self.link
+    }
+
+    fn info(&self) -> u32 {
+        self.info
+    }
+
     fn addralign(&self) -> u64 {
         self.addralign
     }
+
+    fn entry_size(&self) -> u64 {
+        self.entry_size
+    }
 }
 
-/// An enum abstraction over raw ELF section types.
+/// An enum abstraction over raw ELF section types. The raw value is returned
+/// by [`ElfSection::section_type_raw`].
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
-#[repr(u32)]
 pub enum ElfSectionType {
     /// This value marks the section header as inactive; it does not have an
     /// associated section. Other members of the section header have undefined
     /// values.
-    Unused = 0,
+    Unused,
 
     /// The section holds information defined by the program, whose format and
     /// meaning are determined solely by the program.
-    ProgramSection = 1,
+    ProgramSection,
 
     /// This section holds a linker symbol table.
-    LinkerSymbolTable = 2,
+    LinkerSymbolTable,
 
     /// The section holds a string table.
-    StringTable = 3,
+    StringTable,
 
     /// The section holds relocation entries with explicit addends, such as type
     /// Elf32_Rela for the 32-bit class of object files. An object file may have
     /// multiple relocation sections.
-    RelaRelocation = 4,
+    RelaRelocation,
 
     /// The section holds a symbol hash table.
-    SymbolHashTable = 5,
+    SymbolHashTable,
 
     /// The section holds dynamic linking tables.
-    DynamicLinkingTable = 6,
+    DynamicLinkingTable,
 
     /// This section holds information that marks the file in some way.
-    Note = 7,
+    Note,
 
     /// A section of this type occupies no space in the file but otherwise resembles
     /// `ProgramSection`. Although this section contains no bytes, the
     /// sh_offset member contains the conceptual file offset.
-    Uninitialized = 8,
+    Uninitialized,
 
     /// The section holds relocation entries without explicit addends, such as type
     /// Elf32_Rel for the 32-bit class of object files. An object file may have
     /// multiple relocation sections.
-    RelRelocation = 9,
+    RelRelocation,
 
     /// This section type is reserved but has unspecified semantics.
-    Reserved = 10,
+    Reserved,
 
     /// This section holds a dynamic loader symbol table.
-    DynamicLoaderSymbolTable = 11,
+    DynamicLoaderSymbolTable,
+
+    /// The section holds an array of pointers to initialization functions.
+    InitArray,
+
+    /// The section holds an array of pointers to termination functions.
+    FiniArray,
+
+    /// The section holds an array of pointers to functions that are invoked
+    /// before all other initialization functions.
+    PreInitArray,
+
+    /// The section defines a section group, i.e. a set of sections that are
+    /// related and must be treated specially by the linker.
+    Group,
+
+    /// The section holds the extended section indices of a symbol table, which
+    /// are needed if a file has more than `0xFF00` sections.
+    SymbolTableSectionIndex,
+
+    /// The section holds relative relocation entries in a compact format.
+    RelrRelocation,
+
+    /// The section holds GNU object attributes (GNU-specific).
+    GnuAttributes,
+
+    /// The section holds a GNU-style symbol hash table (GNU-specific).
+    GnuHash,
+
+    /// The section holds a list of prelink library dependencies (GNU-specific).
+    GnuLibList,
+
+    /// The section holds a checksum of the DSO content (GNU-specific).
+    Checksum,
+
+    /// The section holds the version definitions of symbols (GNU-specific).
+    GnuVersionDefinition,
+
+    /// The section holds the version requirements of symbols (GNU-specific).
+    GnuVersionNeeded,
+
+    /// The section holds the version of each symbol (GNU-specific).
+    GnuVersionSymbol,
 
     /// Values in this inclusive range (`[0x6000_0000, 0x6FFF_FFFF)`) are
     /// reserved for environment-specific semantics.
-    EnvironmentSpecific = 0x6000_0000,
+    EnvironmentSpecific,
 
     /// Values in this inclusive range (`[0x7000_0000, 0x7FFF_FFFF)`) are
     /// reserved for processor-specific semantics.
-    ProcessorSpecific = 0x7000_0000,
+    ProcessorSpecific,
+
+    /// Values in this inclusive range (`[0x8000_0000, 0xFFFF_FFFF)`) are
+    /// reserved for application programs.
+    UserSpecific,
+
+    /// A section type that is not defined by the ELF specification. Holds the
+    /// raw value.
+    Unknown(u32),
+}
+
+impl From<u32> for ElfSectionType {
+    fn from(typ: u32) -> Self {
+        match typ {
+            0 => ElfSectionType::Unused,
+            1 => ElfSectionType::ProgramSection,
+            2 => ElfSectionType::LinkerSymbolTable,
+            3 => ElfSectionType::StringTable,
+            4 => ElfSectionType::RelaRelocation,
+            5 => ElfSectionType::SymbolHashTable,
+            6 => ElfSectionType::DynamicLinkingTable,
+            7 => ElfSectionType::Note,
+            8 => ElfSectionType::Uninitialized,
+            9 => ElfSectionType::RelRelocation,
+            10 => ElfSectionType::Reserved,
+            11 => ElfSectionType::DynamicLoaderSymbolTable,
+            14 => ElfSectionType::InitArray,
+            15 => ElfSectionType::FiniArray,
+            16 => ElfSectionType::PreInitArray,
+            17 => ElfSectionType::Group,
+            18 => ElfSectionType::SymbolTableSectionIndex,
+            19 => ElfSectionType::RelrRelocation,
+            0x6FFF_FFF5 => ElfSectionType::GnuAttributes,
+            0x6FFF_FFF6 => ElfSectionType::GnuHash,
+            0x6FFF_FFF7 => ElfSectionType::GnuLibList,
+            0x6FFF_FFF8 => ElfSectionType::Checksum,
+            0x6FFF_FFFD => ElfSectionType::GnuVersionDefinition,
+            0x6FFF_FFFE => ElfSectionType::GnuVersionNeeded,
+            0x6FFF_FFFF => ElfSectionType::GnuVersionSymbol,
+            0x6000_0000..=0x6FFF_FFFF => ElfSectionType::EnvironmentSpecific,
+            0x7000_0000..=0x7FFF_FFFF => ElfSectionType::ProcessorSpecific,
+            0x8000_0000..=0xFFFF_FFFF => ElfSectionType::UserSpecific,
+            typ => ElfSectionType::Unknown(typ),
+        }
+    }
+}
+
+/// Returns the raw value of the section type. The ranges
+/// [`ElfSectionType::EnvironmentSpecific`], [`ElfSectionType::ProcessorSpecific`]
+/// and [`ElfSectionType::UserSpecific`] are converted to their first value.
+impl From<ElfSectionType> for u32 {
+    fn from(typ: ElfSectionType) -> Self {
+        match typ {
+            ElfSectionType::Unused => 0,
+            ElfSectionType::ProgramSection => 1,
+            ElfSectionType::LinkerSymbolTable => 2,
+            ElfSectionType::StringTable => 3,
+            ElfSectionType::RelaRelocation => 4,
+            ElfSectionType::SymbolHashTable => 5,
+            ElfSectionType::DynamicLinkingTable => 6,
+            ElfSectionType::Note => 7,
+            ElfSectionType::Uninitialized => 8,
+            ElfSectionType::RelRelocation => 9,
+            ElfSectionType::Reserved => 10,
+            ElfSectionType::DynamicLoaderSymbolTable => 11,
+            ElfSectionType::InitArray => 14,
+            ElfSectionType::FiniArray => 15,
+            ElfSectionType::PreInitArray => 16,
+            ElfSectionType::Group => 17,
+            ElfSectionType::SymbolTableSectionIndex => 18,
+            ElfSectionType::RelrRelocation => 19,
+            ElfSectionType::GnuAttributes => 0x6FFF_FFF5,
+            ElfSectionType::GnuHash => 0x6FFF_FFF6,
+            ElfSectionType::GnuLibList => 0x6FFF_FFF7,
+            ElfSectionType::Checksum => 0x6FFF_FFF8,
+            ElfSectionType::GnuVersionDefinition => 0x6FFF_FFFD,
+            ElfSectionType::GnuVersionNeeded => 0x6FFF_FFFE,
+            ElfSectionType::GnuVersionSymbol => 0x6FFF_FFFF,
+            ElfSectionType::EnvironmentSpecific => 0x6000_0000,
+            ElfSectionType::ProcessorSpecific => 0x7000_0000,
+            ElfSectionType::UserSpecific => 0x8000_0000,
+            ElfSectionType::Unknown(typ) => typ,
+        }
+    }
 }
 
 bitflags! {
@@ -393,7 +805,31 @@ bitflags! {
 
         /// The section contains executable machine instructions.
         const EXECUTABLE = 0x4;
-        // plus environment-specific use at 0x0F000000
+
+        /// The data in the section may be merged to eliminate duplication.
+        const MERGE = 0x10;
+
+        /// The section consists of null-terminated character strings.
+        const STRINGS = 0x20;
+
+        /// The `info` field of the section header holds a section header table index.
+        const INFO_LINK = 0x40;
+
+        /// The section must keep its order relative to the section it links to.
+        const LINK_ORDER = 0x80;
+
+        /// The section requires special OS-specific processing to avoid incorrect behavior.
+        const OS_NONCONFORMING = 0x100;
+
+        /// The section is a member of a section group.
+        const GROUP = 0x200;
+
+        /// The section holds thread-local storage.
+        const TLS = 0x400;
+
+        /// The section holds compressed data.
+        const COMPRESSED = 0x800;
+        // plus environment-specific use at 0x0FF00000
         // plus processor-specific use at 0xF0000000
     }
 }
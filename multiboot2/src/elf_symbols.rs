@@ -0,0 +1,467 @@
+use crate::elf_sections::{c_str_at, ElfClass, ElfSection};
+use core::convert::TryFrom;
+use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::ptr;
+
+/// The section index of undefined symbols (`SHN_UNDEF`).
+const SHN_UNDEF: u16 = 0;
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct ElfSymbolInner32 {
+    name_index: u32,
+    value: u32,
+    size: u32,
+    info: u8,
+    other: u8,
+    shndx: u16,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct ElfSymbolInner64 {
+    name_index: u32,
+    info: u8,
+    other: u8,
+    shndx: u16,
+    value: u64,
+    size: u64,
+}
+
+/// The symbol table (`.symtab`) of the kernel together with its string table.
+///
+/// It is obtained via [`crate::ElfSectionsTag::symbol_table`]. The symbols are
+/// read from the memory where the bootloader loaded the sections, adjusted by
+/// the offset that was passed to [`crate::load_with_offset`]. The values of the
+/// symbols are the (virtual) addresses the kernel was linked at.
+#[derive(Clone, Copy)]
+pub struct SymbolTable<'a> {
+    symbols: &'a [u8],
+    entry_size: usize,
+    strings: &'a [u8],
+    class: ElfClass,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Creates the symbol table from the section headers of the symbol table
+    /// and of the linked string table. Returns `None`, if the size of the
+    /// symbol entries is too small for the ELF class or if the tables don't
+    /// fit into the address space.
+    ///
+    /// # Safety
+    /// Both sections must be loaded into memory by the bootloader.
+    pub(crate) unsafe fn new(
+        symtab: &ElfSection,
+        strtab: &ElfSection,
+        class: ElfClass,
+    ) -> Option<Self> {
+        let entry_size = usize::try_from(symtab.entry_size()).ok()?;
+        let min_entry_size = match class {
+            ElfClass::Elf32 => size_of::<ElfSymbolInner32>(),
+            ElfClass::Elf64 => size_of::<ElfSymbolInner64>(),
+        };
+        if entry_size < min_entry_size {
+            return None;
+        }
+        Some(Self {
+            symbols: symtab.loaded_bytes()?,
+            entry_size,
+            strings: strtab.loaded_bytes()?,
+            class,
+        })
+    }
+
+    /// Returns the number of symbols, including the null symbol at index 0.
+    pub fn len(&self) -> usize {
+        self.symbols.len() / self.entry_size
+    }
+
+    /// Returns `true`, if there are no symbols at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over all symbols, including the null symbol at
+    /// index 0.
+    pub fn symbols(&self) -> ElfSymbolIter<'a> {
+        ElfSymbolIter {
+            table: *self,
+            index: 0,
+        }
+    }
+
+    /// Returns the symbol with the given index, if it is within the table.
+    pub fn symbol(&self, index: usize) -> Option<ElfSymbol<'a>> {
+        let start = index.checked_mul(self.entry_size)?;
+        let bytes = self
+            .symbols
+            .get(start..start.checked_add(self.entry_size)?)?;
+        let symbol = match self.class {
+            ElfClass::Elf32 => {
+                let inner =
+                    unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<ElfSymbolInner32>()) };
+                ElfSymbol {
+                    name_index: inner.name_index,
+                    value: inner.value.into(),
+                    size: inner.size.into(),
+                    info: inner.info,
+                    other: inner.other,
+                    shndx: inner.shndx,
+                    strings: self.strings,
+                }
+            }
+            ElfClass::Elf64 => {
+                let inner =
+                    unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<ElfSymbolInner64>()) };
+                ElfSymbol {
+                    name_index: inner.name_index,
+                    value: inner.value,
+                    size: inner.size,
+                    info: inner.info,
+                    other: inner.other,
+                    shndx: inner.shndx,
+                    strings: self.strings,
+                }
+            }
+        };
+        Some(symbol)
+    }
+
+    /// Finds the defined function or object symbol that contains `addr` and
+    /// returns its name and the offset of `addr` into the symbol. Symbols with
+    /// a size of 0 only match their exact address. If several symbols match,
+    /// the one that starts closest to `addr` wins.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // print a stack trace
+    /// for return_address in stack_walker {
+    ///     match symbols.symbolize(return_address) {
+    ///         Some((name, offset)) => println!("{:#x} {}+{:#x}", return_address, name, offset),
+    ///         None => println!("{:#x} <unknown>", return_address),
+    ///     }
+    /// }
+    /// ```
+    pub fn symbolize(&self, addr: u64) -> Option<(&'a str, u64)> {
+        self.symbols()
+            .filter(|symbol| {
+                symbol.section_index() != SHN_UNDEF
+                    && matches!(
+                        symbol.symbol_type(),
+                        ElfSymbolType::NoType | ElfSymbolType::Object | ElfSymbolType::Function
+                    )
+                    && symbol.contains(addr)
+            })
+            .filter_map(|symbol| {
+                let name = symbol.name().filter(|name| !name.is_empty())?;
+                Some((name, symbol.value()))
+            })
+            .max_by_key(|&(_, value)| value)
+            .map(|(name, value)| (name, addr - value))
+    }
+}
+
+impl Debug for SymbolTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SymbolTable")
+            .field("symbols (count)", &self.len())
+            .field("strings (size)", &self.strings.len())
+            .finish()
+    }
+}
+
+/// An iterator over the symbols of a [`SymbolTable`].
+#[derive(Clone, Debug)]
+pub struct ElfSymbolIter<'a> {
+    table: SymbolTable<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for ElfSymbolIter<'a> {
+    type Item = ElfSymbol<'a>;
+
+    fn next(&mut self) -> Option<ElfSymbol<'a>> {
+        let symbol = self.table.symbol(self.index)?;
+        self.index += 1;
+        Some(symbol)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.table.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ElfSymbolIter<'_> {}
+
+/// A single symbol of the [`SymbolTable`], decoded from either an `Elf32_Sym`
+/// or an `Elf64_Sym`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ElfSymbol<'a> {
+    name_index: u32,
+    value: u64,
+    size: u64,
+    info: u8,
+    other: u8,
+    shndx: u16,
+    strings: &'a [u8],
+}
+
+impl<'a> ElfSymbol<'a> {
+    /// Returns the name of the symbol from the string table. Returns `None`,
+    /// if the name is not within the string table or is not valid UTF-8.
+    pub fn name(&self) -> Option<&'a str> {
+        let name = c_str_at(self.strings, self.name_index as usize)?;
+        core::str::from_utf8(name).ok()
+    }
+
+    /// Returns the index of the name of the symbol in the string table.
+    pub fn name_index(&self) -> u32 {
+        self.name_index
+    }
+
+    /// Returns the value of the symbol, which is usually its address.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the size of the symbol in bytes or 0, if the size is unknown.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the raw `st_info` field, which holds the type and the binding.
+    pub fn info(&self) -> u8 {
+        self.info
+    }
+
+    /// Returns the type of the symbol.
+    pub fn symbol_type(&self) -> ElfSymbolType {
+        match self.info & 0xf {
+            0 => ElfSymbolType::NoType,
+            1 => ElfSymbolType::Object,
+            2 => ElfSymbolType::Function,
+            3 => ElfSymbolType::Section,
+            4 => ElfSymbolType::File,
+            5 => ElfSymbolType::Common,
+            6 => ElfSymbolType::Tls,
+            typ => ElfSymbolType::Unknown(typ),
+        }
+    }
+
+    /// Returns the binding of the symbol.
+    pub fn binding(&self) -> ElfSymbolBinding {
+        match self.info >> 4 {
+            0 => ElfSymbolBinding::Local,
+            1 => ElfSymbolBinding::Global,
+            2 => ElfSymbolBinding::Weak,
+            binding => ElfSymbolBinding::Unknown(binding),
+        }
+    }
+
+    /// Returns the raw `st_other` field, which holds the visibility.
+    pub fn other(&self) -> u8 {
+        self.other
+    }
+
+    /// Returns the index of the section the symbol is defined in. This is 0
+    /// (`SHN_UNDEF`) for undefined symbols.
+    pub fn section_index(&self) -> u16 {
+        self.shndx
+    }
+
+    /// Checks if `addr` is within the symbol. Symbols with a size of 0 only
+    /// contain their exact value.
+    fn contains(&self, addr: u64) -> bool {
+        match addr.checked_sub(self.value) {
+            Some(offset) => offset < self.size || addr == self.value,
+            None => false,
+        }
+    }
+}
+
+impl Debug for ElfSymbol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ElfSymbol")
+            .field("name", &self.name())
+            .field("value", &self.value)
+            .field("size", &self.size)
+            .field("symbol_type", &self.symbol_type())
+            .field("binding", &self.binding())
+            .field("section_index", &self.shndx)
+            .finish()
+    }
+}
+
+/// The type of an [`ElfSymbol`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElfSymbolType {
+    /// The type of the symbol is not specified.
+    NoType,
+    /// The symbol is a data object, such as a variable or an array.
+    Object,
+    /// The symbol is a function or other executable code.
+    Function,
+    /// The symbol is associated with a section.
+    Section,
+    /// The symbol is the name of the source file.
+    File,
+    /// The symbol labels an uninitialized common block.
+    Common,
+    /// The symbol specifies a thread-local storage entity.
+    Tls,
+    /// An environment- or processor-specific type or one that is not defined
+    /// by the ELF specification. Holds the raw value.
+    Unknown(u8),
+}
+
+/// The binding of an [`ElfSymbol`], which determines its visibility for the
+/// linker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElfSymbolBinding {
+    /// The symbol is not visible outside of its object file.
+    Local,
+    /// The symbol is visible to all object files.
+    Global,
+    /// Like a global symbol, but with a lower precedence.
+    Weak,
+    /// An environment- or processor-specific binding or one that is not
+    /// defined by the ELF specification. Holds the raw value.
+    Unknown(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ElfSymbolBinding, ElfSymbolType};
+    use crate::elf_sections::elf_sections_tag;
+    use crate::{ElfSectionNameError, Tag, TagType};
+
+    const LOAD_OFFSET: usize = 0x1000;
+
+    /// Returns a 64-bit section header in bytes in native endian format.
+    fn section(name: u32, typ: u32, addr: u64, size: u64, link: u32, entry_size: u64) -> [u64; 8] {
+        [
+            u64::from(name) | u64::from(typ) << 32,
+            0,
+            addr,
+            0,
+            size,
+            u64::from(link),
+            0,
+            entry_size,
+        ]
+    }
+
+    /// Returns a 64-bit symbol in bytes in native endian format.
+    fn symbol(name: u32, info: u8, shndx: u16, value: u64, size: u64) -> std::vec::Vec<u8> {
+        let mut bytes = std::vec::Vec::new();
+        bytes.extend(&name.to_ne_bytes());
+        bytes.extend(&[info, 0]);
+        bytes.extend(&shndx.to_ne_bytes());
+        bytes.extend(&value.to_ne_bytes());
+        bytes.extend(&size.to_ne_bytes());
+        bytes
+    }
+
+    /// Returns an ELF sections tag with the given 64-bit section headers and
+    /// the section name string table at index 2.
+    fn tag_bytes(sections: &[[u64; 8]]) -> std::vec::Vec<u32> {
+        let mut tag = std::vec![
+            u32::from(TagType::ElfSections),
+            20 + sections.len() as u32 * 64,
+            sections.len() as u32, // number of sections
+            64,                    // entry size
+            2,                     // shndx
+        ];
+        tag.extend(sections.iter().flat_map(|section| {
+            section
+                .iter()
+                .flat_map(|field| [*field as u32, (*field >> 32) as u32].to_vec())
+        }));
+        tag
+    }
+
+    #[test]
+    fn test_symbolize() {
+        let strings = b"\0.symtab\0.strtab\0main\0DATA\0extern\0label\0".to_vec();
+        let symbols = [
+            symbol(0, 0, 0, 0, 0),
+            symbol(1, 0x03, 1, 0, 0),            // section
+            symbol(17, 0x12, 1, 0x1_0000, 0x40), // main: global function
+            symbol(22, 0x11, 1, 0x1_0100, 0x10), // DATA: global object
+            symbol(27, 0x12, 0, 0x1_0200, 0x10), // extern: undefined
+            symbol(34, 0x00, 1, 0x1_0010, 0),    // label: local, without a size
+        ]
+        .concat();
+
+        // the sections are loaded at their address plus the offset
+        let symtab_addr = (symbols.as_ptr() as usize - LOAD_OFFSET) as u64;
+        let strtab_addr = (strings.as_ptr() as usize - LOAD_OFFSET) as u64;
+        let sections = [
+            section(0, 0, 0, 0, 0, 0),
+            section(1, 2, symtab_addr, symbols.len() as u64, 2, 24),
+            section(9, 3, strtab_addr, strings.len() as u64, 0, 0),
+        ];
+        let tag = tag_bytes(&sections);
+        let tag = unsafe { tag.as_ptr().cast::<Tag>().as_ref().unwrap() };
+        let elf_tag = unsafe { elf_sections_tag(tag, LOAD_OFFSET) }
+            .unwrap()
+            .unwrap();
+        let table = elf_tag.symbol_table().unwrap();
+        assert_eq!(table.len(), 6);
+        assert_eq!(table.symbols().len(), 6);
+
+        let main = table.symbol(2).unwrap();
+        assert_eq!(main.name(), Some("main"));
+        assert_eq!(main.value(), 0x1_0000);
+        assert_eq!(main.size(), 0x40);
+        assert_eq!(main.symbol_type(), ElfSymbolType::Function);
+        assert_eq!(main.binding(), ElfSymbolBinding::Global);
+        assert_eq!(main.section_index(), 1);
+        assert!(table.symbol(6).is_none());
+
+        assert_eq!(table.symbolize(0x1_0000), Some(("main", 0)));
+        assert_eq!(table.symbolize(0x1_0008), Some(("main", 8)));
+        // the closer label wins
+        assert_eq!(table.symbolize(0x1_0010), Some(("label", 0)));
+        assert_eq!(table.symbolize(0x1_003f), Some(("main", 0x3f)));
+        assert_eq!(table.symbolize(0x1_0040), None);
+        assert_eq!(table.symbolize(0x1_0104), Some(("DATA", 4)));
+        // undefined symbols are ignored
+        assert_eq!(table.symbolize(0x1_0200), None);
+        assert_eq!(table.symbolize(0), None);
+    }
+
+    #[test]
+    fn test_symbol_table_not_loaded() {
+        let strings = b"\0.symtab\0.strtab\0".to_vec();
+        // the bootloader didn't load the symbol table
+        let sections = [
+            section(0, 0, 0, 0, 0, 0),
+            section(1, 2, 0, 48, 2, 24),
+            section(9, 3, strings.as_ptr() as u64, strings.len() as u64, 0, 0),
+        ];
+        let tag = tag_bytes(&sections);
+        let tag = unsafe { tag.as_ptr().cast::<Tag>().as_ref().unwrap() };
+        let elf_tag = unsafe { elf_sections_tag(tag, 0) }.unwrap().unwrap();
+        assert!(elf_tag.symbol_table().is_none());
+        assert_eq!(elf_tag.section(1).unwrap().name(), Ok(".symtab"));
+
+        // neither the symbol table nor the string table are loaded
+        let sections = [
+            section(0, 0, 0, 0, 0, 0),
+            section(1, 2, 0, 48, 2, 24),
+            section(9, 3, 0, strings.len() as u64, 0, 0),
+        ];
+        let tag = tag_bytes(&sections);
+        let tag = unsafe { tag.as_ptr().cast::<Tag>().as_ref().unwrap() };
+        let elf_tag = unsafe { elf_sections_tag(tag, 0) }.unwrap().unwrap();
+        assert!(elf_tag.symbol_table().is_none());
+        assert_eq!(
+            elf_tag.section(1).unwrap().name(),
+            Err(ElfSectionNameError::MissingStringTable)
+        );
+    }
+}
@@ -1,7 +1,15 @@
 use crate::tag_type::Tag;
 use crate::Reader;
+use core::mem::size_of;
 use core::slice;
 
+/// The size of the part of the framebuffer tag that is common to all framebuffer
+/// types, i.e. everything before `color_info`.
+const FRAMEBUFFER_TAG_BASE_SIZE: usize = 32;
+
+/// The size of `color_info` for the type `RGB`.
+const RGB_COLOR_INFO_SIZE: usize = 6;
+
 /// The VBE Framebuffer information Tag.
 #[derive(Debug, PartialEq, Eq)]
 pub struct FramebufferTag<'a> {
@@ -78,7 +86,23 @@ pub struct FramebufferColor {
     pub blue: u8,
 }
 
-pub fn framebuffer_tag(tag: &Tag) -> FramebufferTag {
+/// Errors that can occur while parsing the type of a [`FramebufferTag`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramebufferTypeError {
+    /// The framebuffer type is not known by this crate, for example because of
+    /// a bug in the bootloader or a newer version of the specification. Holds
+    /// the raw type.
+    Unknown(u8),
+}
+
+/// Parses the framebuffer tag. Returns `None`, if the tag is too small to hold
+/// the properties of its framebuffer type, and an error, if the framebuffer
+/// type is unknown.
+pub fn framebuffer_tag(tag: &Tag) -> Option<Result<FramebufferTag<'_>, FramebufferTypeError>> {
+    let tag_size = tag.size as usize;
+    if tag_size < FRAMEBUFFER_TAG_BASE_SIZE {
+        return None;
+    }
     let mut reader = Reader::new(tag as *const Tag);
     reader.skip(8);
     let address = reader.read_u64();
@@ -92,15 +116,22 @@ pub fn framebuffer_tag(tag: &Tag) -> FramebufferTag {
     let buffer_type = match type_no {
         0 => {
             let num_colors = reader.read_u32();
+            let palette_size = (num_colors as usize).checked_mul(size_of::<FramebufferColor>())?;
+            if tag_size < FRAMEBUFFER_TAG_BASE_SIZE + size_of::<u32>() + palette_size {
+                return None;
+            }
             let palette = unsafe {
                 slice::from_raw_parts(
                     reader.current_address() as *const FramebufferColor,
                     num_colors as usize,
                 )
-            } as &'static [FramebufferColor];
+            };
             FramebufferType::Indexed { palette }
         }
         1 => {
+            if tag_size < FRAMEBUFFER_TAG_BASE_SIZE + RGB_COLOR_INFO_SIZE {
+                return None;
+            }
             let red_pos = reader.read_u8(); // These refer to the bit positions of the LSB of each field
             let red_mask = reader.read_u8(); // And then the length of the field from LSB to MSB
             let green_pos = reader.read_u8();
@@ -123,15 +154,15 @@ pub fn framebuffer_tag(tag: &Tag) -> FramebufferTag {
             }
         }
         2 => FramebufferType::Text,
-        _ => panic!("Unknown framebuffer type: {}", type_no),
+        _ => return Some(Err(FramebufferTypeError::Unknown(type_no))),
     };
 
-    FramebufferTag {
+    Some(Ok(FramebufferTag {
         address,
         pitch,
         width,
         height,
         bpp,
         buffer_type,
-    }
+    }))
 }
@@ -1,16 +1,30 @@
-use crate::TagType;
+use crate::{TagTrait, TagType, TagTypeId};
+use core::mem::size_of;
 
 /// If the image has relocatable header tag, this tag contains the image's
 /// base physical address.
 #[derive(Debug)]
 #[repr(C)]
 pub struct ImageLoadPhysAddr {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     load_base_addr: u32,
 }
 
+unsafe impl TagTrait for ImageLoadPhysAddr {
+    const ID: TagType = TagType::LoadBaseAddr;
+}
+
 impl ImageLoadPhysAddr {
+    /// Creates a new tag with the given load base address.
+    pub fn new(load_base_addr: u32) -> Self {
+        Self {
+            typ: TagType::LoadBaseAddr.into(),
+            size: size_of::<Self>() as u32,
+            load_base_addr,
+        }
+    }
+
     /// Returns the load base address.
     pub fn load_base_addr(&self) -> u32 {
         self.load_base_addr
@@ -34,29 +34,43 @@
 //! ## MSRV
 //! The MSRV is 1.52.1 stable.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // this crate can use std in tests only
 #[cfg_attr(test, macro_use)]
 #[cfg(test)]
 extern crate std;
 
 use core::fmt;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::Range;
 
+pub use apm::{ApmFlags, ApmTag};
 pub use boot_loader_name::BootLoaderNameTag;
+pub use bootdev::BootdevTag;
 pub use command_line::CommandLineTag;
 pub use efi::{EFIImageHandle32, EFIImageHandle64, EFISdt32, EFISdt64};
 pub use elf_sections::{
-    ElfSection, ElfSectionFlags, ElfSectionIter, ElfSectionType, ElfSectionsTag,
+    ElfSection, ElfSectionFlags, ElfSectionIter, ElfSectionNameError, ElfSectionType,
+    ElfSectionsTag, ElfSectionsTagError,
+};
+pub use elf_symbols::{ElfSymbol, ElfSymbolBinding, ElfSymbolIter, ElfSymbolType, SymbolTable};
+pub use framebuffer::{
+    FramebufferColor, FramebufferField, FramebufferTag, FramebufferType, FramebufferTypeError,
 };
-pub use framebuffer::{FramebufferColor, FramebufferField, FramebufferTag, FramebufferType};
 pub use image_load_addr::ImageLoadPhysAddr;
 pub use memory_map::{
-    EFIMemoryAreaType, EFIMemoryDesc, EFIMemoryMapTag, MemoryArea, MemoryAreaIter, MemoryAreaType,
-    MemoryMapTag,
+    BasicMemoryInfoTag, EFIBootServicesNotExited, EFIMemoryAreaType, EFIMemoryAttribute,
+    EFIMemoryDesc, EFIMemoryMapTag, MemoryArea, MemoryAreaIter, MemoryAreaType,
+    MemoryMapBufferTooSmall, MemoryMapTag, MemoryRegion, MemoryRegionIter, MemoryRegionKind,
 };
 pub use module::{ModuleIter, ModuleTag};
-pub use rsdp::{RsdpV1Tag, RsdpV2Tag};
-pub use tag_type::TagType;
-use tag_type::{Tag, TagIter};
+pub use network::{DhcpOption, DhcpOptionIter, DhcpPacket, Ipv4AddrList, NetworkIter, NetworkTag};
+pub use rsdp::{Rsdp, RsdpError, RsdpV1Tag, RsdpV2Tag};
+pub use smbios::{SmbiosStructure, SmbiosStructureIter, SmbiosTag};
+pub use tag_type::{Tag, TagIter, TagTrait, TagType, TagTypeId};
 pub use vbe_info::{
     VBECapabilities, VBEControlInfo, VBEDirectColorAttributes, VBEField, VBEInfoTag,
     VBEMemoryModel, VBEModeAttributes, VBEModeInfo, VBEWindowAttributes,
@@ -65,18 +79,28 @@ pub use vbe_info::{
 #[macro_use]
 extern crate bitflags;
 
+mod apm;
 mod boot_loader_name;
+mod bootdev;
 mod command_line;
 mod efi;
 mod elf_sections;
+mod elf_symbols;
 mod framebuffer;
 mod image_load_addr;
 mod memory_map;
 mod module;
+mod network;
 mod rsdp;
+mod smbios;
 mod tag_type;
 mod vbe_info;
 
+#[cfg(feature = "acpi")]
+pub mod acpi;
+#[cfg(feature = "builder")]
+pub mod builder;
+
 /// Magic number that a multiboot2-compliant boot loader will store in `eax` register
 /// right before handoff to the payload (the kernel). This value can be used to check,
 /// that the kernel was indeed booted via multiboot2.
@@ -109,7 +133,7 @@ pub const MULTIBOOT2_BOOTLOADER_MAGIC: u32 = 0x36d76289;
 ///   memory is not (identity) mapped (UEFI does only identity mapping).
 /// * The memory at `address` must not be modified after calling `load` or the
 ///   program may observe unsychronized mutation.
-pub unsafe fn load(address: usize) -> Result<BootInformation, MbiLoadError> {
+pub unsafe fn load(address: usize) -> Result<BootInformation<'static>, MbiLoadError> {
     load_with_offset(address, 0)
 }
 
@@ -135,7 +159,7 @@ pub unsafe fn load(address: usize) -> Result<BootInformation, MbiLoadError> {
 pub unsafe fn load_with_offset(
     address: usize,
     offset: usize,
-) -> Result<BootInformation, MbiLoadError> {
+) -> Result<BootInformation<'static>, MbiLoadError> {
     let address = address + offset;
     let null_ptr = address == 0;
     let eight_byte_aligned = address & 0b111 == 0;
@@ -144,9 +168,12 @@ pub unsafe fn load_with_offset(
     }
 
     let multiboot = &*(address as *const BootInformationInner);
-    // Check if total size is a multiple of 8.
+    // Check if total size is a multiple of 8 and big enough for the
+    // fixed part and the end tag.
     // See MbiLoadError::IllegalTotalSize for comments
-    if multiboot.total_size & 0b111 != 0 {
+    if multiboot.total_size & 0b111 != 0
+        || (multiboot.total_size as usize) < size_of::<BootInformationInner>() + size_of::<Tag>()
+    {
         return Err(MbiLoadError::IllegalTotalSize(multiboot.total_size));
     }
     if !multiboot.has_valid_end_tag() {
@@ -156,6 +183,7 @@ pub unsafe fn load_with_offset(
     Ok(BootInformation {
         inner: multiboot,
         offset,
+        phantom: PhantomData,
     })
 }
 
@@ -170,14 +198,41 @@ pub enum MbiLoadError {
     /// (Not in spec, but it is implicitly the case, because the begin of MBI
     /// and all tags are 8-byte aligned and the end tag is exactly 8 byte long).
     IllegalTotalSize(u32),
+    /// The buffer that was passed to [`BootInformation::from_bytes`] is smaller than
+    /// the total size of the multiboot2 information structure.
+    BufferTooSmall,
     /// End tag missing. Each multiboot2 header requires to have an end tag.
     NoEndTag,
 }
 
+/// Error type of [`BootInformation::usable_memory_regions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsableMemoryError {
+    /// The buffer is too small for the usable memory areas.
+    BufferTooSmall,
+    /// The physical location of the kernel is unknown: its allocated ELF
+    /// sections start beyond the end of the memory map, i.e. at the virtual
+    /// addresses of a higher-half kernel, and there is no image load base
+    /// address tag. Use [`BootInformation::usable_memory_regions_with_kernel`]
+    /// with the physical range of the kernel instead.
+    UnknownKernelLocation,
+}
+
+impl From<MemoryMapBufferTooSmall> for UsableMemoryError {
+    fn from(_: MemoryMapBufferTooSmall) -> Self {
+        UsableMemoryError::BufferTooSmall
+    }
+}
+
 /// A Multiboot 2 Boot Information struct.
-pub struct BootInformation {
+///
+/// All tags are only accessed within the bounds given by the `total_size` of the
+/// structure and by the `size` of each tag. Tags that are too small for their type
+/// are ignored.
+pub struct BootInformation<'a> {
     inner: *const BootInformationInner,
     offset: usize,
+    phantom: PhantomData<&'a [u8]>,
 }
 
 #[derive(Clone, Copy)]
@@ -187,7 +242,42 @@ struct BootInformationInner {
     _reserved: u32,
 }
 
-impl BootInformation {
+impl<'a> BootInformation<'a> {
+    /// Load the multiboot boot information struct from a byte slice, for example
+    /// from a dump of the structure. Contrary to [`load`], this is safe, as every
+    /// access to the structure and its tags is checked against the bounds of `bytes`.
+    ///
+    /// The slice must begin at an 8-byte aligned address, as the spec requires it.
+    ///
+    /// Note that some tags contain physical addresses that point to memory outside
+    /// of the structure, such as the string table of the ELF sections. Following
+    /// them is only sound in the environment that the structure was created for.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// use multiboot2::BootInformation;
+    ///
+    /// let bytes: &[u8] = read_mbi_dump();
+    /// let boot_info = BootInformation::from_bytes(bytes).unwrap();
+    /// println!("{:?}", boot_info);
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, MbiLoadError> {
+        let address = bytes.as_ptr() as usize;
+        if address & 0b111 != 0 {
+            return Err(MbiLoadError::IllegalAddress);
+        }
+        if bytes.len() < size_of::<BootInformationInner>() {
+            return Err(MbiLoadError::BufferTooSmall);
+        }
+        let inner = unsafe { &*(bytes.as_ptr() as *const BootInformationInner) };
+        if inner.total_size as usize > bytes.len() {
+            return Err(MbiLoadError::BufferTooSmall);
+        }
+        // SAFETY: All memory covered by `total_size` is part of `bytes`.
+        unsafe { load(address) }
+    }
+
     /// Get the start address of the boot info.
     pub fn start_address(&self) -> usize {
         self.inner as usize
@@ -209,134 +299,329 @@ impl BootInformation {
         self.get().total_size as usize
     }
 
-    /// Search for the ELF Sections tag.
-    pub fn elf_sections_tag(&self) -> Option<ElfSectionsTag> {
-        self.get_tag(TagType::ElfSections)
-            .map(|tag| unsafe { elf_sections::elf_sections_tag(tag, self.offset) })
+    /// Search for the ELF Sections tag. Returns an error, if the section
+    /// headers are malformed.
+    pub fn elf_sections_tag(&self) -> Option<Result<ElfSectionsTag, ElfSectionsTagError>> {
+        self.find_tag(TagType::ElfSections)
+            .and_then(|tag| unsafe { elf_sections::elf_sections_tag(tag, self.offset) })
+    }
+
+    /// Search for the basic memory info tag.
+    pub fn basic_memory_info_tag(&self) -> Option<&BasicMemoryInfoTag> {
+        self.get_tag()
+    }
+
+    /// Search for the BIOS boot device tag.
+    pub fn bootdev_tag(&self) -> Option<&BootdevTag> {
+        self.get_tag()
     }
 
     /// Search for the Memory map tag.
     pub fn memory_map_tag(&self) -> Option<&MemoryMapTag> {
-        self.get_tag(TagType::Mmap)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const MemoryMapTag) })
+        self.get_tag()
+    }
+
+    /// Computes the available memory of the memory map minus all memory that
+    /// is occupied by the boot information itself, the modules and the kernel,
+    /// so that it can be used to seed a frame allocator. The areas are written
+    /// into `buffer`, sorted and without overlaps (see
+    /// [`MemoryMapTag::normalized_memory_areas`]). The areas are not aligned
+    /// to page boundaries.
+    ///
+    /// The kernel occupies the extent of its allocated ELF sections (see
+    /// [`ElfSectionsTag::kernel_extent`]). If the image load base address tag
+    /// is present, the extent is moved to the load base address. Otherwise,
+    /// the extent is reserved as is. The section addresses of a higher-half
+    /// kernel are virtual, however, and the ELF sections tag doesn't tell where
+    /// the kernel is loaded in physical memory. If the extent starts beyond the
+    /// end of the memory map, [`UsableMemoryError::UnknownKernelLocation`] is
+    /// returned; use [`Self::usable_memory_regions_with_kernel`] then. Sections
+    /// that are not allocated, such as the symbol table, are reserved at their
+    /// address, as the bootloader loads them there.
+    ///
+    /// Returns no area, if there is no memory map tag.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 64];
+    /// for area in boot_info.usable_memory_regions(&mut buffer).unwrap() {
+    ///     frame_allocator.add(area.start_address(), area.end_address());
+    /// }
+    /// ```
+    pub fn usable_memory_regions<'b>(
+        &self,
+        buffer: &'b mut [MemoryArea],
+    ) -> Result<&'b [MemoryArea], UsableMemoryError> {
+        let tag = match self.memory_map_tag() {
+            Some(tag) => tag,
+            None => return Ok(&buffer[..0]),
+        };
+        let kernel = self
+            .elf_sections_tag()
+            .and_then(Result::ok)
+            .and_then(|tag| tag.kernel_extent())
+            .map(|extent| match self.load_base_addr() {
+                Some(load_base) => {
+                    let load_base = u64::from(load_base.load_base_addr());
+                    Ok(load_base..load_base.saturating_add(extent.end - extent.start))
+                }
+                None => {
+                    let memory_end = tag
+                        .all_memory_areas()
+                        .map(|area| area.end_address())
+                        .max()
+                        .unwrap_or(0);
+                    if extent.start < memory_end {
+                        Ok(extent)
+                    } else {
+                        Err(UsableMemoryError::UnknownKernelLocation)
+                    }
+                }
+            })
+            .transpose()?;
+        self.usable_memory_regions_with_kernel(buffer, kernel.unwrap_or(0..0))
+    }
+
+    /// Like [`Self::usable_memory_regions`], but reserves the given physical
+    /// range of the kernel instead of deriving it from the ELF sections. This
+    /// is required for higher-half kernels, if the bootloader doesn't provide
+    /// the image load base address tag. The kernel usually knows its physical
+    /// range from symbols of its linker script.
+    pub fn usable_memory_regions_with_kernel<'b>(
+        &self,
+        buffer: &'b mut [MemoryArea],
+        kernel: Range<u64>,
+    ) -> Result<&'b [MemoryArea], UsableMemoryError> {
+        let tag = match self.memory_map_tag() {
+            Some(tag) => tag,
+            None => return Ok(&buffer[..0]),
+        };
+
+        let mbi_start = (self.start_address() - self.offset) as u64;
+        let mbi = mbi_start..mbi_start + self.total_size() as u64;
+        let modules = self
+            .module_tags()
+            .map(|module| u64::from(module.start_address())..u64::from(module.end_address()));
+        let elf_sections = self.elf_sections_tag().and_then(Result::ok);
+        let unallocated_sections = elf_sections
+            .iter()
+            .flat_map(|tag| tag.sections())
+            .filter(|section| !section.is_allocated() && section.start_address() != 0)
+            .map(|section| section.start_address()..section.end_address());
+
+        let reserved = core::iter::once(mbi)
+            .chain(modules)
+            .chain(core::iter::once(kernel))
+            .chain(unallocated_sections);
+        Ok(tag.usable_memory_areas(buffer, reserved)?)
     }
 
     /// Get an iterator of all module tags.
-    pub fn module_tags(&self) -> ModuleIter {
+    pub fn module_tags(&self) -> ModuleIter<'_> {
         module::module_iter(self.tags())
     }
 
     /// Search for the BootLoader name tag.
     pub fn boot_loader_name_tag(&self) -> Option<&BootLoaderNameTag> {
-        self.get_tag(TagType::BootLoaderName)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const BootLoaderNameTag) })
+        self.get_tag()
     }
 
     /// Search for the Command line tag.
     pub fn command_line_tag(&self) -> Option<&CommandLineTag> {
-        self.get_tag(TagType::Cmdline)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const CommandLineTag) })
+        self.get_tag()
     }
 
-    /// Search for the VBE framebuffer tag.
-    pub fn framebuffer_tag(&self) -> Option<FramebufferTag> {
-        self.get_tag(TagType::Framebuffer)
-            .map(framebuffer::framebuffer_tag)
+    /// Search for the VBE framebuffer tag. Returns an error, if the framebuffer
+    /// type is unknown.
+    pub fn framebuffer_tag(&self) -> Option<Result<FramebufferTag<'_>, FramebufferTypeError>> {
+        self.find_tag(TagType::Framebuffer)
+            .and_then(framebuffer::framebuffer_tag)
+    }
+
+    /// Search for the APM table tag.
+    pub fn apm_tag(&self) -> Option<&ApmTag> {
+        self.get_tag()
     }
 
     /// Search for the EFI 32-bit SDT tag.
     pub fn efi_sdt_32_tag(&self) -> Option<&EFISdt32> {
-        self.get_tag(TagType::Efi32)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const EFISdt32) })
+        self.get_tag()
     }
 
     /// Search for the EFI 64-bit SDT tag.
     pub fn efi_sdt_64_tag(&self) -> Option<&EFISdt64> {
-        self.get_tag(TagType::Efi64)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const EFISdt64) })
+        self.get_tag()
+    }
+
+    /// Search for the SMBIOS tag.
+    pub fn smbios_tag(&self) -> Option<&SmbiosTag> {
+        self.get_tag()
     }
 
     /// Search for the (ACPI 1.0) RSDP tag.
     pub fn rsdp_v1_tag(&self) -> Option<&RsdpV1Tag> {
-        self.get_tag(TagType::AcpiV1)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const RsdpV1Tag) })
+        self.get_tag()
     }
 
     /// Search for the (ACPI 2.0 or later) RSDP tag.
     pub fn rsdp_v2_tag(&self) -> Option<&RsdpV2Tag> {
-        self.get_tag(TagType::AcpiV2)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const RsdpV2Tag) })
+        self.get_tag()
     }
 
-    /// Search for the EFI Memory map tag.
+    /// Returns a view over the RSDP of the [`RsdpV2Tag`], if present, or of the [`RsdpV1Tag`]
+    /// otherwise. The RSDP is not validated, see [`Rsdp::validate`].
+    pub fn rsdp(&self) -> Option<Rsdp<'_>> {
+        self.rsdp_v2_tag()
+            .map(Rsdp::V2)
+            .or_else(|| self.rsdp_v1_tag().map(Rsdp::V1))
+    }
+
+    /// Get an iterator of all network tags. There is one tag per network card.
+    pub fn network_tags(&self) -> NetworkIter<'_> {
+        network::network_iter(self.tags())
+    }
+
+    /// Search for the EFI Memory map tag. Returns `None`, if the EFI boot
+    /// services were not exited (see [`Self::efi_bs_not_exited_tag`]).
     pub fn efi_memory_map_tag(&self) -> Option<&EFIMemoryMapTag> {
         // If the EFIBootServicesNotExited is present, then we should not use
         // the memory map, as it could still be in use.
-        match self.get_tag(TagType::EfiBs) {
+        match self.efi_bs_not_exited_tag() {
             Some(_tag) => None,
-            None => self
-                .get_tag(TagType::EfiMmap)
-                .map(|tag| unsafe { &*(tag as *const Tag as *const EFIMemoryMapTag) }),
+            None => self.get_tag(),
         }
     }
 
+    /// Search for the EFI Memory map tag, even if the EFI boot services were
+    /// not exited.
+    ///
+    /// If the boot services are still active, the memory map is only a
+    /// snapshot of the time the bootloader handed over control. Every call to
+    /// the boot services can allocate or free memory and thereby change the
+    /// actual memory map, so memory of type `EfiConventionalMemory` must not
+    /// be used as long as the boot services are in use. To obtain a current
+    /// memory map, use the `GetMemoryMap()` boot service.
+    pub fn efi_memory_map_tag_unconditional(&self) -> Option<&EFIMemoryMapTag> {
+        self.get_tag()
+    }
+
+    /// Search for the tag that indicates that the EFI boot services were not
+    /// exited.
+    pub fn efi_bs_not_exited_tag(&self) -> Option<&EFIBootServicesNotExited> {
+        self.get_tag()
+    }
+
+    /// Returns an iterator over the memory regions of the EFI memory map or, if
+    /// there is no EFI memory map with at least one descriptor, of the legacy
+    /// memory map. The regions are classified by a common [`MemoryRegionKind`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let usable = boot_info
+    ///     .memory_regions()
+    ///     .filter(|region| region.kind() == MemoryRegionKind::Usable);
+    /// ```
+    pub fn memory_regions(&self) -> MemoryRegionIter<'_> {
+        memory_map::memory_region_iter(self.efi_memory_map_tag(), self.memory_map_tag())
+    }
+
     /// Search for the EFI 32-bit image handle pointer.
     pub fn efi_32_ih(&self) -> Option<&EFIImageHandle32> {
-        self.get_tag(TagType::Efi32Ih)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const EFIImageHandle32) })
+        self.get_tag()
     }
 
     /// Search for the EFI 64-bit image handle pointer.
     pub fn efi_64_ih(&self) -> Option<&EFIImageHandle64> {
-        self.get_tag(TagType::Efi64Ih)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const EFIImageHandle64) })
+        self.get_tag()
     }
 
     /// Search for the Image Load Base Physical Address.
     pub fn load_base_addr(&self) -> Option<&ImageLoadPhysAddr> {
-        self.get_tag(TagType::LoadBaseAddr)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const ImageLoadPhysAddr) })
+        self.get_tag()
     }
 
     /// Search for the VBE information tag.
-    pub fn vbe_info_tag(&self) -> Option<&'static VBEInfoTag> {
-        self.get_tag(TagType::Vbe)
-            .map(|tag| unsafe { &*(tag as *const Tag as *const VBEInfoTag) })
+    pub fn vbe_info_tag(&self) -> Option<&VBEInfoTag> {
+        self.get_tag()
     }
 
     fn get(&self) -> &BootInformationInner {
         unsafe { &*self.inner }
     }
 
-    fn get_tag(&self, typ: TagType) -> Option<&Tag> {
+    /// Searches for the first tag with the given type.
+    fn find_tag(&self, typ: TagType) -> Option<&Tag> {
         self.tags().find(|tag| tag.typ == typ)
     }
 
-    fn tags(&self) -> TagIter {
-        TagIter::new(unsafe { self.inner.offset(1) } as *const _)
+    /// Searches for the first tag of type [`TagTrait::ID`] and casts it to `T`.
+    /// Tags that are smaller than [`TagTrait::MIN_SIZE`] are ignored. This works
+    /// for all tags of this crate as well as for custom tags.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// use multiboot2::{CommandLineTag, TagTrait, TagType, TagTypeId};
+    ///
+    /// let cmdline = boot_info.get_tag::<CommandLineTag>().unwrap();
+    ///
+    /// #[repr(C)]
+    /// struct CustomTag {
+    ///     typ: TagTypeId,
+    ///     size: u32,
+    ///     foo: u32,
+    /// }
+    ///
+    /// unsafe impl TagTrait for CustomTag {
+    ///     const ID: TagType = TagType::Unknown(0x1337);
+    /// }
+    ///
+    /// let custom = boot_info.get_tag::<CustomTag>().unwrap();
+    /// ```
+    pub fn get_tag<T: TagTrait>(&self) -> Option<&T> {
+        self.find_tag(T::ID)
+            .filter(|tag| tag.size as usize >= T::MIN_SIZE)
+            .map(|tag| unsafe { &*(tag as *const Tag as *const T) })
+    }
+
+    /// Returns an iterator over all tags of the MBI, including tags of a type that is
+    /// unknown to this crate. The iteration stops at the end tag or at the first tag
+    /// that is not completely within the MBI.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// for tag in boot_info.tags() {
+    ///     println!("tag {:?} with {} bytes of payload", tag.typ(), tag.payload().len());
+    /// }
+    /// ```
+    pub fn tags(&self) -> TagIter<'_> {
+        TagIter::new(
+            unsafe { self.inner.offset(1) } as *const _,
+            self.end_address(),
+        )
     }
 }
 
 impl BootInformationInner {
     fn has_valid_end_tag(&self) -> bool {
-        const END_TAG: Tag = Tag {
-            typ: TagType::End,
-            size: 8,
-        };
+        const END_TAG_SIZE: u32 = 8;
 
         let self_ptr = self as *const _;
-        let end_tag_addr = self_ptr as usize + (self.total_size - END_TAG.size) as usize;
+        let end_tag_addr = self_ptr as usize + (self.total_size - END_TAG_SIZE) as usize;
         let end_tag = unsafe { &*(end_tag_addr as *const Tag) };
 
-        end_tag.typ == END_TAG.typ && end_tag.size == END_TAG.size
+        end_tag.typ == TagType::End && end_tag.size == END_TAG_SIZE
     }
 }
 
 // SAFETY: BootInformation contains a const ptr to memory that is never mutated.
 // Sending this pointer to other threads is sound.
-unsafe impl Send for BootInformation {}
+unsafe impl Send for BootInformation<'_> {}
 
-impl fmt::Debug for BootInformation {
+impl fmt::Debug for BootInformation<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         /// Limit how many Elf-Sections should be debug-formatted.
         /// Can be thousands of sections for a Rust binary => this is useless output.
@@ -362,6 +647,8 @@ impl fmt::Debug for BootInformation {
                     .and_then(|x| x.command_line().ok())
                     .unwrap_or(""),
             )
+            .field("basic_memory_info", &self.basic_memory_info_tag())
+            .field("bootdev", &self.bootdev_tag())
             .field("memory_areas", &self.memory_map_tag())
             // so far, I didn't found a nice way to connect the iterator with ".field()" because
             // the iterator isn't Debug
@@ -370,6 +657,7 @@ impl fmt::Debug for BootInformation {
 
         let elf_sections_tag_entries_count = self
             .elf_sections_tag()
+            .and_then(Result::ok)
             .map(|x| x.sections().count())
             .unwrap_or(0);
 
@@ -380,6 +668,7 @@ impl fmt::Debug for BootInformation {
                 "elf_sections_tags",
                 &self
                     .elf_sections_tag()
+                    .and_then(Result::ok)
                     .map(|x| x.sections())
                     .unwrap_or_default(),
             );
@@ -509,6 +798,211 @@ mod tests {
         assert!(bi.command_line_tag().is_none());
     }
 
+    #[test]
+    fn from_bytes() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 32]);
+        let bytes: Bytes = Bytes([
+            32, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            1, 0, 0, 0, // command line tag type
+            13, 0, 0, 0, // command line tag size
+            110, 97, 109, 101, // command line 'name'
+            0, 0, 0, 0, // command line null + padding
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert_eq!(bytes.0.as_ptr() as usize, bi.start_address());
+        assert_eq!(bytes.0.len(), bi.total_size());
+        assert_eq!(
+            "name",
+            bi.command_line_tag()
+                .expect("tag must be present")
+                .command_line()
+                .expect("must be valid utf8")
+        );
+
+        // not 8-byte aligned
+        assert!(matches!(
+            BootInformation::from_bytes(&bytes.0[4..]),
+            Err(MbiLoadError::IllegalAddress)
+        ));
+        // total_size is bigger than the buffer
+        assert!(matches!(
+            BootInformation::from_bytes(&bytes.0[..24]),
+            Err(MbiLoadError::BufferTooSmall)
+        ));
+        assert!(matches!(
+            BootInformation::from_bytes(&bytes.0[..4]),
+            Err(MbiLoadError::BufferTooSmall)
+        ));
+    }
+
+    #[test]
+    fn from_bytes_illegal_total_size() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 16]);
+        let bytes: Bytes = Bytes([
+            8, 0, 0, 0, // total_size, too small for the end tag
+            0, 0, 0, 0, // reserved
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        assert!(matches!(
+            BootInformation::from_bytes(&bytes.0),
+            Err(MbiLoadError::IllegalTotalSize(8))
+        ));
+    }
+
+    /// A tag whose `size` field exceeds the MBI must not be read.
+    #[test]
+    fn from_bytes_corrupt_tag_size() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 40]);
+        let bytes: Bytes = Bytes([
+            40, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            2, 0, 0, 0, // boot loader name tag type
+            13, 0, 0, 0, // boot loader name tag size
+            110, 97, 109, 101, // boot loader name 'name'
+            0, 0, 0, 0, // boot loader name null + padding
+            1, 0, 0, 0, // command line tag type
+            0, 1, 0, 0, // command line tag size: 256, beyond the end of the MBI
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert!(bi.boot_loader_name_tag().is_some());
+        assert!(bi.command_line_tag().is_none());
+        assert_eq!(bi.tags().count(), 1);
+    }
+
+    /// Tags that are smaller than the structure of their type must be ignored.
+    #[test]
+    fn from_bytes_tag_too_small() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 48]);
+        let bytes: Bytes = Bytes([
+            48, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            21, 0, 0, 0, // load base addr tag type
+            8, 0, 0, 0, // load base addr tag size: no space for the address
+            8, 0, 0, 0, // framebuffer tag type
+            16, 0, 0, 0, // framebuffer tag size: no space for the properties
+            0, 0, 0, 253, // framebuffer low dword of address
+            0, 0, 0, 0, // framebuffer high dword of address
+            9, 0, 0, 0, // elf sections tag type
+            8, 0, 0, 0, // elf sections tag size: no space for the fields
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert_eq!(bi.tags().count(), 3);
+        assert!(bi.load_base_addr().is_none());
+        assert!(bi.framebuffer_tag().is_none());
+        assert!(bi.elf_sections_tag().is_none());
+    }
+
+    /// Tags of an unknown type must be skipped by the typed getters but be
+    /// visible through the generic tag iterator.
+    #[test]
+    fn unknown_tag() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 48]);
+        let bytes: Bytes = Bytes([
+            48, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            0x37, 0x13, 0, 0, // unknown tag type: 0x1337
+            14, 0, 0, 0, // unknown tag size
+            1, 2, 3, 4, // unknown tag payload
+            5, 6, 0, 0, // unknown tag payload + padding
+            2, 0, 0, 0, // boot loader name tag type
+            13, 0, 0, 0, // boot loader name tag size
+            110, 97, 109, 101, // boot loader name 'name'
+            0, 0, 0, 0, // boot loader name null + padding
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert_eq!("name", bi.boot_loader_name_tag().unwrap().name().unwrap());
+
+        let mut tags = bi.tags();
+        let tag = tags.next().unwrap();
+        assert_eq!(tag.typ(), TagType::Unknown(0x1337));
+        assert_eq!(tag.typ_id(), TagTypeId::new(0x1337));
+        assert_eq!(tag.size(), 14);
+        assert_eq!(tag.payload(), &[1, 2, 3, 4, 5, 6]);
+        let tag = tags.next().unwrap();
+        assert_eq!(tag.typ(), TagType::BootLoaderName);
+        assert_eq!(tag.payload(), b"name\0");
+        assert!(tags.next().is_none());
+    }
+
+    /// Custom tags can be looked up via [`TagTrait`].
+    #[test]
+    fn custom_tag() {
+        #[repr(C)]
+        struct CustomTag {
+            typ: TagTypeId,
+            size: u32,
+            foo: u32,
+            bar: u32,
+        }
+
+        unsafe impl TagTrait for CustomTag {
+            const ID: TagType = TagType::Unknown(0x1337);
+        }
+
+        #[repr(C, align(8))]
+        struct Bytes([u8; 48]);
+        let bytes: Bytes = Bytes([
+            48, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            0x37, 0x13, 0, 0, // custom tag type: 0x1337
+            12, 0, 0, 0, // custom tag size: too small for the custom tag
+            1, 0, 0, 0, // foo
+            0, 0, 0, 0, // padding
+            0x37, 0x13, 0, 0, // custom tag type: 0x1337
+            16, 0, 0, 0, // custom tag size
+            1, 0, 0, 0, // foo
+            2, 0, 0, 0, // bar
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        // The first custom tag is too small and must not be returned.
+        assert!(bi.get_tag::<CustomTag>().is_none());
+
+        let bytes = Bytes([
+            48, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            1, 0, 0, 0, // command line tag type
+            13, 0, 0, 0, // command line tag size
+            110, 97, 109, 101, // command line 'name'
+            0, 0, 0, 0, // command line null + padding
+            0x37, 0x13, 0, 0, // custom tag type: 0x1337
+            16, 0, 0, 0, // custom tag size
+            1, 0, 0, 0, // foo
+            2, 0, 0, 0, // bar
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let tag = bi.get_tag::<CustomTag>().unwrap();
+        assert_eq!(tag.typ, TagType::Unknown(0x1337));
+        assert_eq!(tag.size, 16);
+        assert_eq!(tag.foo, 1);
+        assert_eq!(tag.bar, 2);
+        assert_eq!(
+            "name",
+            bi.get_tag::<CommandLineTag>()
+                .unwrap()
+                .command_line()
+                .unwrap()
+        );
+    }
+
     #[test]
     fn name_tag() {
         #[repr(C, align(8))]
@@ -583,7 +1077,7 @@ mod tests {
         use framebuffer::{FramebufferField, FramebufferTag, FramebufferType};
         assert_eq!(
             bi.framebuffer_tag(),
-            Some(FramebufferTag {
+            Some(Ok(FramebufferTag {
                 address: 4244635648,
                 pitch: 5120,
                 width: 1280,
@@ -603,7 +1097,7 @@ mod tests {
                         size: 8
                     }
                 }
-            })
+            }))
         )
     }
 
@@ -638,7 +1132,7 @@ mod tests {
         assert_eq!(bytes.0.len(), bi.total_size());
         use framebuffer::{FramebufferColor, FramebufferType};
         assert!(bi.framebuffer_tag().is_some());
-        let fbi = bi.framebuffer_tag().unwrap();
+        let fbi = bi.framebuffer_tag().unwrap().unwrap();
         assert_eq!(fbi.address, 4244635648);
         assert_eq!(fbi.pitch, 5120);
         assert_eq!(fbi.width, 1280);
@@ -674,6 +1168,34 @@ mod tests {
         }
     }
 
+    /// An unknown framebuffer type must be reported as error instead of a panic.
+    #[test]
+    fn framebuffer_tag_unknown_type() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 56]);
+        let bytes: Bytes = Bytes([
+            56, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            8, 0, 0, 0, // framebuffer tag type
+            40, 0, 0, 0, // framebuffer tag size
+            0, 0, 0, 253, // framebuffer low dword of address
+            0, 0, 0, 0, // framebuffer high dword of address
+            0, 20, 0, 0, // framebuffer pitch
+            0, 5, 0, 0, // framebuffer width
+            208, 2, 0, 0, // framebuffer height
+            32, 3, 0, 0, // framebuffer bpp, type: unknown, reserved word
+            16, 8, 8, 8, // framebuffer color info
+            0, 8, 0, 0, // framebuffer color info, padding word
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert_eq!(
+            bi.framebuffer_tag(),
+            Some(Err(FramebufferTypeError::Unknown(3)))
+        );
+    }
+
     #[test]
     /// Compile time test for `FramebufferTag`.
     fn framebuffer_tag_size() {
@@ -1142,17 +1664,17 @@ mod tests {
         assert_eq!(addr, bi.start_address());
         assert_eq!(addr + bytes.len(), bi.end_address());
         assert_eq!(bytes.len(), bi.total_size());
-        let es = bi.elf_sections_tag().unwrap();
+        let es = bi.elf_sections_tag().unwrap().unwrap();
         let mut s = es.sections();
         let s1 = s.next().unwrap();
-        assert_eq!(".rodata", s1.name());
+        assert_eq!(Ok(".rodata"), s1.name());
         assert_eq!(0xFFFF_8000_0010_0000, s1.start_address());
         assert_eq!(0xFFFF_8000_0010_3000, s1.end_address());
         assert_eq!(0x0000_0000_0000_3000, s1.size());
         assert_eq!(ElfSectionFlags::ALLOCATED, s1.flags());
         assert_eq!(ElfSectionType::ProgramSection, s1.section_type());
         let s2 = s.next().unwrap();
-        assert_eq!(".text", s2.name());
+        assert_eq!(Ok(".text"), s2.name());
         assert_eq!(0xFFFF_8000_0010_3000, s2.start_address());
         assert_eq!(0xFFFF_8000_0010_C000, s2.end_address());
         assert_eq!(0x0000_0000_0000_9000, s2.size());
@@ -1162,7 +1684,7 @@ mod tests {
         );
         assert_eq!(ElfSectionType::ProgramSection, s2.section_type());
         let s3 = s.next().unwrap();
-        assert_eq!(".data", s3.name());
+        assert_eq!(Ok(".data"), s3.name());
         assert_eq!(0xFFFF_8000_0010_C000, s3.start_address());
         assert_eq!(0xFFFF_8000_0010_E000, s3.end_address());
         assert_eq!(0x0000_0000_0000_2000, s3.size());
@@ -1172,7 +1694,7 @@ mod tests {
         );
         assert_eq!(ElfSectionType::ProgramSection, s3.section_type());
         let s4 = s.next().unwrap();
-        assert_eq!(".bss", s4.name());
+        assert_eq!(Ok(".bss"), s4.name());
         assert_eq!(0xFFFF_8000_0010_E000, s4.start_address());
         assert_eq!(0xFFFF_8000_0011_3000, s4.end_address());
         assert_eq!(0x0000_0000_0000_5000, s4.size());
@@ -1182,7 +1704,7 @@ mod tests {
         );
         assert_eq!(ElfSectionType::Uninitialized, s4.section_type());
         let s5 = s.next().unwrap();
-        assert_eq!(".data.rel.ro", s5.name());
+        assert_eq!(Ok(".data.rel.ro"), s5.name());
         assert_eq!(0xFFFF_8000_0011_3000, s5.start_address());
         assert_eq!(0xFFFF_8000_0011_3000, s5.end_address());
         assert_eq!(0x0000_0000_0000_0000, s5.size());
@@ -1192,27 +1714,68 @@ mod tests {
         );
         assert_eq!(ElfSectionType::ProgramSection, s5.section_type());
         let s6 = s.next().unwrap();
-        assert_eq!(".symtab", s6.name());
+        assert_eq!(Ok(".symtab"), s6.name());
         assert_eq!(0x0000_0000_0011_3000, s6.start_address());
         assert_eq!(0x0000_0000_0011_5BE0, s6.end_address());
         assert_eq!(0x0000_0000_0000_2BE0, s6.size());
         assert_eq!(ElfSectionFlags::empty(), s6.flags());
         assert_eq!(ElfSectionType::LinkerSymbolTable, s6.section_type());
         let s7 = s.next().unwrap();
-        assert_eq!(".strtab", s7.name());
+        assert_eq!(Ok(".strtab"), s7.name());
         assert_eq!(0x0000_0000_0011_5BE0, s7.start_address());
         assert_eq!(0x0000_0000_0011_9371, s7.end_address());
         assert_eq!(0x0000_0000_0000_3791, s7.size());
         assert_eq!(ElfSectionFlags::empty(), s7.flags());
         assert_eq!(ElfSectionType::StringTable, s7.section_type());
         let s8 = s.next().unwrap();
-        assert_eq!(".shstrtab", s8.name());
+        assert_eq!(Ok(".shstrtab"), s8.name());
         assert_eq!(string_addr, s8.start_address());
         assert_eq!(string_addr + string_bytes.len() as u64, s8.end_address());
         assert_eq!(string_bytes.len() as u64, s8.size());
         assert_eq!(ElfSectionFlags::empty(), s8.flags());
         assert_eq!(ElfSectionType::StringTable, s8.section_type());
         assert!(s.next().is_none());
+        assert_eq!(
+            es.find_by_name(".bss").unwrap().start_address(),
+            0xFFFF_8000_0010_E000
+        );
+        assert!(es.find_by_name(".got").is_none());
+        assert_eq!(
+            es.section_containing(0xFFFF_8000_0010_3ABC).unwrap().name(),
+            Ok(".text")
+        );
+        assert_eq!(
+            es.section_containing(0xFFFF_8000_0011_2FFF).unwrap().name(),
+            Ok(".bss")
+        );
+        // the symbol table isn't allocated
+        assert!(es.section_containing(0x0011_4000).is_none());
+        assert!(es.section_containing(0xFFFF_8000_0011_3000).is_none());
+        assert_eq!(
+            es.kernel_extent(),
+            Some(0xFFFF_8000_0010_0000..0xFFFF_8000_0011_3000)
+        );
+        let mut all = es.all_sections();
+        let null = all.next().unwrap();
+        assert_eq!(0, null.index());
+        assert_eq!(ElfSectionType::Unused, null.section_type());
+        assert_eq!(Ok(".rodata"), all.next().unwrap().name());
+        assert_eq!(7, all.count());
+        assert_eq!(
+            (1..9).collect::<std::vec::Vec<u32>>(),
+            es.sections()
+                .map(|section| section.index())
+                .collect::<std::vec::Vec<u32>>()
+        );
+        let symtab = es.section(6).unwrap();
+        assert_eq!(Ok(".symtab"), symtab.name());
+        assert_eq!(6, symtab.index());
+        assert_eq!(Ok(".strtab"), es.section(symtab.link()).unwrap().name());
+        assert_eq!(
+            ElfSectionType::Unused,
+            es.section(0).unwrap().section_type()
+        );
+        assert!(es.section(9).is_none());
         let mut mm = bi.memory_map_tag().unwrap().memory_areas();
         let mm1 = mm.next().unwrap();
         assert_eq!(0x00000000, mm1.start_address());
@@ -1225,6 +1788,50 @@ mod tests {
         assert_eq!(0x7EE_0000, mm2.size());
         assert_eq!(MemoryAreaType::Available, mm2.typ());
         assert!(mm.next().is_none());
+        // without an EFI memory map, the legacy memory map is used
+        let mut regions = bi.memory_regions();
+        assert_eq!(
+            Some(MemoryRegion::new(0, 0x9_FC00, MemoryRegionKind::Usable)),
+            regions.next()
+        );
+        assert_eq!(
+            Some(MemoryRegion::new(
+                0x9_FC00,
+                0x400,
+                MemoryRegionKind::Reserved
+            )),
+            regions.next()
+        );
+        assert_eq!(
+            bi.memory_map_tag().unwrap().all_memory_areas().count(),
+            bi.memory_regions().count()
+        );
+
+        // Test the basic memory info tag
+        let basic_memory_info = bi.basic_memory_info_tag().unwrap();
+        assert_eq!(639, basic_memory_info.memory_lower());
+        assert_eq!(129920, basic_memory_info.memory_upper());
+
+        // Test the APM tag
+        let apm = bi.apm_tag().unwrap();
+        assert_eq!(0x0102, apm.version());
+        assert_eq!(0xf000, apm.cseg());
+        assert_eq!(0xd4cf, apm.offset());
+        assert_eq!(0xf000, apm.cseg_16());
+        assert_eq!(0xf000, apm.dseg());
+        assert_eq!(
+            ApmFlags::PROTECTED_MODE_16_BIT | ApmFlags::PROTECTED_MODE_32_BIT,
+            apm.flags()
+        );
+        assert_eq!(0xfff0, apm.cseg_len());
+        assert_eq!(0xfff0, apm.cseg_16_len());
+        assert_eq!(0xfff0, apm.dseg_len());
+
+        // Test the BIOS boot device tag
+        let bootdev = bi.bootdev_tag().unwrap();
+        assert_eq!(0xe0, bootdev.biosdev());
+        assert_eq!(None, bootdev.partition());
+        assert_eq!(None, bootdev.sub_partition());
 
         // Test the RSDP tag
         let rsdp_old = bi.rsdp_v1_tag().unwrap();
@@ -1233,6 +1840,10 @@ mod tests {
         assert_eq!("BOCHS ", rsdp_old.oem_id().unwrap());
         assert_eq!(0, rsdp_old.revision());
         assert_eq!(0x7FE18DC, rsdp_old.rsdt_address());
+        let rsdp = bi.rsdp().unwrap();
+        assert_eq!(Ok(()), rsdp.validate());
+        assert_eq!(0x7FE18DC, rsdp.rsdt_address());
+        assert_eq!(None, rsdp.xsdt_address());
 
         assert!(bi.module_tags().next().is_none());
         assert_eq!(
@@ -1251,7 +1862,7 @@ mod tests {
         );
 
         // Test the Framebuffer tag
-        let fbi = bi.framebuffer_tag().unwrap();
+        let fbi = bi.framebuffer_tag().unwrap().unwrap();
         assert_eq!(fbi.address, 753664);
         assert_eq!(fbi.pitch, 160);
         assert_eq!(fbi.width, 80);
@@ -1268,7 +1879,7 @@ mod tests {
             168, 0, 0, 0, // total_size
             0, 0, 0, 0, // reserved
             9, 0, 0, 0, // elf symbols tag type
-            20, 2, 0, 0, // elf symbols tag size
+            148, 0, 0, 0, // elf symbols tag size
             2, 0, 0, 0, // elf symbols num
             64, 0, 0, 0, // elf symbols entsize
             1, 0, 0, 0, // elf symbols shndx
@@ -1324,16 +1935,287 @@ mod tests {
         assert_eq!(addr, bi.start_address());
         assert_eq!(addr + bytes.0.len(), bi.end_address());
         assert_eq!(bytes.0.len(), bi.total_size() as usize);
-        let es = bi.elf_sections_tag().unwrap();
+        let es = bi.elf_sections_tag().unwrap().unwrap();
         let mut s = es.sections();
         let s1 = s.next().unwrap();
-        assert_eq!(".shstrtab", s1.name());
+        assert_eq!(Ok(".shstrtab"), s1.name());
         assert_eq!(string_addr, s1.start_address());
         assert_eq!(string_addr + string_bytes.0.len() as u64, s1.end_address());
         assert_eq!(string_bytes.0.len() as u64, s1.size());
         assert_eq!(ElfSectionFlags::empty(), s1.flags());
         assert_eq!(ElfSectionType::StringTable, s1.section_type());
         assert!(s.next().is_none());
+
+        // a name index beyond the string table
+        bytes.0[92] = 11;
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let es = bi.elf_sections_tag().unwrap().unwrap();
+        let s1 = es.sections().next().unwrap();
+        assert_eq!(Err(ElfSectionNameError::OutOfBounds), s1.name());
+        // the string table index is `SHN_UNDEF`
+        bytes.0[24] = 0;
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let es = bi.elf_sections_tag().unwrap().unwrap();
+        let s1 = es.sections().next().unwrap();
+        assert_eq!(Err(ElfSectionNameError::MissingStringTable), s1.name());
+    }
+
+    /// Section types that are not known must not lead to a panic and a
+    /// missing string table must not be read.
+    #[test]
+    fn elf_sections_unknown_types() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 168]);
+        let bytes: Bytes = Bytes([
+            168, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            9, 0, 0, 0, // elf symbols tag type
+            148, 0, 0, 0, // elf symbols tag size
+            2, 0, 0, 0, // elf symbols num
+            64, 0, 0, 0, // elf symbols entsize
+            7, 0, 0, 0, // elf symbols shndx: out of range
+            1, 0, 0, 0, // elf symbols entry 0 name
+            14, 0, 0, 0, // elf symbols entry 0 type: init array
+            3, 4, 0, 0, // elf symbols entry 0 flags
+            0, 0, 0, 0, // elf symbols entry 0 flags
+            0, 16, 0, 0, // elf symbols entry 0 addr
+            0, 0, 0, 0, // elf symbols entry 0 addr
+            0, 16, 0, 0, // elf symbols entry 0 offset
+            0, 0, 0, 0, // elf symbols entry 0 offset
+            8, 0, 0, 0, // elf symbols entry 0 size
+            0, 0, 0, 0, // elf symbols entry 0 size
+            5, 0, 0, 0, // elf symbols entry 0 link
+            6, 0, 0, 0, // elf symbols entry 0 info
+            8, 0, 0, 0, // elf symbols entry 0 addralign
+            0, 0, 0, 0, // elf symbols entry 0 addralign
+            8, 0, 0, 0, // elf symbols entry 0 entsize
+            0, 0, 0, 0, // elf symbols entry 0 entsize
+            2, 0, 0, 0, // elf symbols entry 1 name
+            0x45, 0x23, 0x01, 0, // elf symbols entry 1 type: unknown
+            0, 0, 0, 0, // elf symbols entry 1 flags
+            0, 0, 0, 0, // elf symbols entry 1 flags
+            255, 255, 255, 255, // elf symbols entry 1 addr
+            255, 255, 255, 255, // elf symbols entry 1 addr
+            0, 0, 0, 0, // elf symbols entry 1 offset
+            0, 0, 0, 0, // elf symbols entry 1 offset
+            16, 0, 0, 0, // elf symbols entry 1 size
+            0, 0, 0, 0, // elf symbols entry 1 size
+            0, 0, 0, 0, // elf symbols entry 1 link
+            0, 0, 0, 0, // elf symbols entry 1 info
+            1, 0, 0, 0, // elf symbols entry 1 addralign
+            0, 0, 0, 0, // elf symbols entry 1 addralign
+            0, 0, 0, 0, // elf symbols entry 1 entsize
+            0, 0, 0, 0, // elf symbols entry 1 entsize
+            0, 0, 0, 0, // elf symbols padding
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        let es = bi.elf_sections_tag().unwrap().unwrap();
+        let mut s = es.sections();
+        let s1 = s.next().unwrap();
+        assert_eq!(ElfSectionType::InitArray, s1.section_type());
+        assert_eq!(Err(ElfSectionNameError::MissingStringTable), s1.name());
+        assert_eq!(
+            ElfSectionFlags::WRITABLE | ElfSectionFlags::ALLOCATED | ElfSectionFlags::TLS,
+            s1.flags()
+        );
+        assert_eq!(0x1000, s1.offset());
+        assert_eq!(5, s1.link());
+        assert_eq!(6, s1.info());
+        assert_eq!(8, s1.entry_size());
+        let s2 = s.next().unwrap();
+        assert_eq!(ElfSectionType::Unknown(0x12345), s2.section_type());
+        assert_eq!(0x12345, s2.section_type_raw());
+        assert_eq!(u64::MAX, s2.end_address());
+        assert!(s.next().is_none());
+    }
+
+    /// Section headers of an unsupported size or beyond the tag must be rejected.
+    #[test]
+    fn elf_sections_malformed() {
+        #[repr(C, align(8))]
+        struct Bytes([u8; 40]);
+        let mut bytes: Bytes = Bytes([
+            40, 0, 0, 0, // total_size
+            0, 0, 0, 0, // reserved
+            9, 0, 0, 0, // elf symbols tag type
+            20, 0, 0, 0, // elf symbols tag size
+            0, 0, 0, 0, // elf symbols num
+            32, 0, 0, 0, // elf symbols entsize: unsupported
+            0, 0, 0, 0, // elf symbols shndx
+            0, 0, 0, 0, // elf symbols padding
+            0, 0, 0, 0, // end tag type
+            8, 0, 0, 0, // end tag size
+        ]);
+        {
+            let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+            assert_eq!(
+                bi.elf_sections_tag().unwrap().unwrap_err(),
+                ElfSectionsTagError::UnsupportedEntrySize(32)
+            );
+        }
+        // one section header of 40 bytes that doesn't fit into the tag
+        bytes.0[16] = 1;
+        bytes.0[20] = 40;
+        let bi = BootInformation::from_bytes(&bytes.0).unwrap();
+        assert_eq!(
+            bi.elf_sections_tag().unwrap().unwrap_err(),
+            ElfSectionsTagError::SectionsOutOfBounds
+        );
+    }
+
+    /// The usable memory must exclude the MBI, the modules, the kernel at its
+    /// load base address and the unallocated ELF sections.
+    #[test]
+    fn usable_memory_regions() {
+        fn push_u32(bytes: &mut std::vec::Vec<u8>, value: u32) {
+            bytes.extend(&value.to_ne_bytes());
+        }
+        fn push_u64(bytes: &mut std::vec::Vec<u8>, value: u64) {
+            bytes.extend(&value.to_ne_bytes());
+        }
+        fn push_section(bytes: &mut std::vec::Vec<u8>, typ: u32, flags: u64, addr: u64, size: u64) {
+            push_u32(bytes, 0);
+            push_u32(bytes, typ);
+            push_u64(bytes, flags);
+            push_u64(bytes, addr);
+            push_u64(bytes, 0);
+            push_u64(bytes, size);
+            bytes.extend(&[0; 24]);
+        }
+
+        fn mbi_bytes(with_load_base: bool) -> std::vec::Vec<u8> {
+            let total_size = if with_load_base { 320 } else { 304 };
+            let mut bytes = std::vec::Vec::new();
+            push_u32(&mut bytes, total_size);
+            push_u32(&mut bytes, 0);
+            // memory map, the last area is patched to contain the MBI
+            push_u32(&mut bytes, TagType::Mmap.into());
+            push_u32(&mut bytes, 112);
+            push_u32(&mut bytes, 24);
+            push_u32(&mut bytes, 0);
+            for &(base, length, typ) in &[
+                (0, 0xa_0000, 1),
+                (0xf_0000, 0x1_0000, 2),
+                (0x10_0000, 0x70_0000, 1),
+                (0, 0, 1),
+            ] {
+                push_u64(&mut bytes, base);
+                push_u64(&mut bytes, length);
+                push_u64(&mut bytes, typ);
+            }
+            // module
+            push_u32(&mut bytes, TagType::Module.into());
+            push_u32(&mut bytes, 17);
+            push_u32(&mut bytes, 0x20_0000);
+            push_u32(&mut bytes, 0x30_0000);
+            bytes.extend(&[0; 8]);
+            // image load base address
+            if with_load_base {
+                push_u32(&mut bytes, TagType::LoadBaseAddr.into());
+                push_u32(&mut bytes, 12);
+                push_u32(&mut bytes, 0x10_0000);
+                push_u32(&mut bytes, 0);
+            }
+            // ELF sections: .text of a higher-half kernel and .symtab
+            push_u32(&mut bytes, TagType::ElfSections.into());
+            push_u32(&mut bytes, 20 + 2 * 64);
+            push_u32(&mut bytes, 2);
+            push_u32(&mut bytes, 64);
+            push_u32(&mut bytes, 0);
+            push_section(&mut bytes, 1, 0x6, 0xffff_8000_0010_0000, 0x5000);
+            push_section(&mut bytes, 2, 0, 0x40_0000, 0x1000);
+            bytes.extend(&[0; 4]);
+            // end tag
+            push_u32(&mut bytes, 0);
+            push_u32(&mut bytes, 8);
+            assert_eq!(bytes.len(), total_size as usize);
+            bytes
+        }
+
+        for &with_load_base in &[true, false] {
+            let bytes = mbi_bytes(with_load_base);
+            let mut mbi: std::vec::Vec<u64> = bytes
+                .chunks(8)
+                .map(|chunk| u64::from_ne_bytes(core::convert::TryInto::try_into(chunk).unwrap()))
+                .collect();
+            let mbi_start = mbi.as_ptr() as u64;
+            let mbi_end = mbi_start + bytes.len() as u64;
+            let page = mbi_start & !0xfff;
+            assert!(page >= 0x80_0000);
+            // the fourth memory area, which starts at byte 96
+            mbi[12] = page;
+            mbi[13] = 0x2000;
+
+            let bi = unsafe { load(mbi_start as usize) }.unwrap();
+            let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 8];
+            let areas = if with_load_base {
+                bi.usable_memory_regions(&mut buffer).unwrap()
+            } else {
+                // the physical location of the higher-half kernel is unknown,
+                // its memory must not become usable
+                assert_eq!(
+                    bi.usable_memory_regions(&mut buffer),
+                    Err(UsableMemoryError::UnknownKernelLocation)
+                );
+                bi.usable_memory_regions_with_kernel(&mut buffer, 0x10_0000..0x10_5000)
+                    .unwrap()
+            };
+            let mut expected = std::vec![
+                MemoryArea::new(0, 0xa_0000, MemoryAreaType::Available),
+                MemoryArea::new(0x10_5000, 0xf_b000, MemoryAreaType::Available),
+                MemoryArea::new(0x30_0000, 0x10_0000, MemoryAreaType::Available),
+                MemoryArea::new(0x40_1000, 0x3f_f000, MemoryAreaType::Available),
+                MemoryArea::new(page, mbi_start - page, MemoryAreaType::Available),
+                MemoryArea::new(mbi_end, page + 0x2000 - mbi_end, MemoryAreaType::Available),
+            ];
+            expected.retain(|area| area.size() != 0);
+            assert_eq!(areas, expected.as_slice());
+        }
+
+        let bytes = mbi_bytes(true);
+        let mut mbi: std::vec::Vec<u64> = bytes
+            .chunks(8)
+            .map(|chunk| u64::from_ne_bytes(core::convert::TryInto::try_into(chunk).unwrap()))
+            .collect();
+        mbi[12] = mbi.as_ptr() as u64 & !0xfff;
+        mbi[13] = 0x2000;
+        let bi = unsafe { load(mbi.as_ptr() as usize) }.unwrap();
+        let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 4];
+        assert_eq!(
+            bi.usable_memory_regions(&mut buffer),
+            Err(UsableMemoryError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn usable_memory_regions_corrupt_area() {
+        // the first memory area exceeds the address space
+        let mbi: std::vec::Vec<u64> = std::vec![
+            80,
+            u64::from(u32::from(TagType::Mmap)) | 64 << 32,
+            24,
+            u64::MAX - 0x10,
+            0x100,
+            1,
+            0x10_0000,
+            0x10_0000,
+            1,
+            8 << 32,
+        ];
+        let bi = unsafe { load(mbi.as_ptr() as usize) }.unwrap();
+        assert_eq!(bi.memory_map_tag().unwrap().all_memory_areas().count(), 2);
+        let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 4];
+        let areas = bi.usable_memory_regions(&mut buffer).unwrap();
+        assert_eq!(
+            areas,
+            &[
+                MemoryArea::new(0x10_0000, 0x10_0000, MemoryAreaType::Available),
+                MemoryArea::new(u64::MAX - 0x10, 0x100, MemoryAreaType::Available),
+            ]
+        );
+        assert_eq!(areas[1].end_address(), u64::MAX);
     }
 
     #[test]
@@ -1341,8 +2223,9 @@ mod tests {
     fn elf_sections_tag_size() {
         use super::ElfSectionsTag;
         unsafe {
-            // `ElfSectionsTagInner` is 12 bytes + 4 in the offset.
-            core::mem::transmute::<[u8; 16], ElfSectionsTag>([0u8; 16]);
+            // `ElfSectionsTagInner` is 8 bytes + 8 for the ELF class (with
+            // padding) + 8 in the offset.
+            core::mem::transmute::<[u8; 24], ElfSectionsTag>([0u8; 24]);
         }
     }
 
@@ -1350,13 +2233,13 @@ mod tests {
     fn efi_memory_map() {
         use memory_map::EFIMemoryAreaType;
         #[repr(C, align(8))]
-        struct Bytes([u8; 72]);
+        struct Bytes([u8; 80]);
         // test that the EFI memory map is detected.
         let bytes: Bytes = Bytes([
-            72, 0, 0, 0, // size
+            80, 0, 0, 0, // size
             0, 0, 0, 0, // reserved
             17, 0, 0, 0, // EFI memory map type
-            56, 0, 0, 0, // EFI memory map size
+            64, 0, 0, 0, // EFI memory map size
             48, 0, 0, 0, // EFI descriptor size
             1, 0, 0, 0, // EFI descriptor version, don't think this matters.
             7, 0, 0, 0, // Type: EfiConventionalMemory
@@ -1369,6 +2252,8 @@ mod tests {
             0, 0, 0, 0, // Extension of pages
             0, 0, 0, 0, // Attributes of this memory range.
             0, 0, 0, 0, // Extension of attributes
+            0, 0, 0, 0, // Descriptor padding (descriptor size is 48)
+            0, 0, 0, 0, // Descriptor padding
             0, 0, 0, 0, // end tag type.
             8, 0, 0, 0, // end tag size.
         ]);
@@ -1381,17 +2266,25 @@ mod tests {
         let efi_memory_map = bi.efi_memory_map_tag().unwrap();
         let mut efi_mmap_iter = efi_memory_map.memory_areas();
         let desc = efi_mmap_iter.next().unwrap();
+        assert!(bi.efi_bs_not_exited_tag().is_none());
         assert_eq!(desc.physical_address(), 0x100000);
         assert_eq!(desc.size(), 16384);
         assert_eq!(desc.typ(), EFIMemoryAreaType::EfiConventionalMemory);
+        let mut regions = bi.memory_regions();
+        assert_eq!(
+            Some(MemoryRegion::new(0x100000, 16384, MemoryRegionKind::Usable)),
+            regions.next()
+        );
+        assert!(regions.next().is_none());
         // test that the EFI memory map is not detected if the boot services
         // are not exited.
-        struct Bytes2([u8; 80]);
+        #[repr(C, align(8))]
+        struct Bytes2([u8; 88]);
         let bytes2: Bytes2 = Bytes2([
-            80, 0, 0, 0, // size
+            88, 0, 0, 0, // size
             0, 0, 0, 0, // reserved
             17, 0, 0, 0, // EFI memory map type
-            56, 0, 0, 0, // EFI memory map size
+            64, 0, 0, 0, // EFI memory map size
             48, 0, 0, 0, // EFI descriptor size
             1, 0, 0, 0, // EFI descriptor version, don't think this matters.
             7, 0, 0, 0, // Type: EfiConventionalMemory
@@ -1404,6 +2297,8 @@ mod tests {
             0, 0, 0, 0, // Extension of pages
             0, 0, 0, 0, // Attributes of this memory range.
             0, 0, 0, 0, // Extension of attributes
+            0, 0, 0, 0, // Descriptor padding (descriptor size is 48)
+            0, 0, 0, 0, // Descriptor padding
             18, 0, 0, 0, // Tag ExitBootServices not terminated.
             8, 0, 0, 0, // Tag ExitBootServices size.
             0, 0, 0, 0, // end tag type.
@@ -1413,6 +2308,13 @@ mod tests {
         let bi = bi.unwrap();
         let efi_mmap = bi.efi_memory_map_tag();
         assert!(efi_mmap.is_none());
+        assert_eq!(
+            Some(&EFIBootServicesNotExited::new()),
+            bi.efi_bs_not_exited_tag()
+        );
+        let efi_mmap = bi.efi_memory_map_tag_unconditional().unwrap();
+        assert_eq!(1, efi_mmap.memory_areas().count());
+        assert!(bi.memory_regions().next().is_none());
     }
 
     #[test]
@@ -1420,8 +2322,8 @@ mod tests {
     fn efi_memory_map_tag_size() {
         use super::EFIMemoryMapTag;
         unsafe {
-            // `EFIMemoryMapTag` is 16 bytes + `EFIMemoryDesc` is 40 bytes.
-            core::mem::transmute::<[u8; 56], EFIMemoryMapTag>([0u8; 56]);
+            // `EFIMemoryMapTag` is 16 bytes, the descriptors follow it.
+            core::mem::transmute::<[u8; 16], EFIMemoryMapTag>([0u8; 16]);
         }
     }
 }
@@ -1,5 +1,70 @@
-use crate::TagType;
+use crate::{TagTrait, TagType, TagTypeId};
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::Range;
+
+/// The size of a [`MemoryMapTag`] without any memory area.
+pub(crate) const MEMORY_MAP_TAG_MIN_SIZE: usize = 16;
+
+/// The size of an [`EFIMemoryMapTag`] without any descriptor.
+pub(crate) const EFI_MEMORY_MAP_TAG_MIN_SIZE: usize = 16;
+
+/// Basic memory information.
+///
+/// This tag includes "basic memory information". This means (legacy) lower and
+/// upper memory: In Real Mode (modeled after the 8086), only the first 1MB of
+/// memory is accessible. Typically, the region between 640KB and 1MB is not
+/// freely usable, because it is used for memory-mapped IO, for instance. The
+/// term "lower memory" refers to those first 640KB of memory that are freely
+/// usable for an application in Real Mode. "Upper memory" then refers to the
+/// next freely usable chunk of memory, starting at 1MB up to about 10MB, in
+/// practice. This is the memory an application running on a 286 (which had a
+/// 24-bit address bus) could use, historically. Nowadays, much bigger chunks of
+/// continuous memory are available at higher addresses, but the Multiboot
+/// standard still references those two terms.
+///
+/// This tag may not be provided by some boot loaders on EFI platforms if EFI
+/// boot services are enabled and available for the loaded image (The EFI boot
+/// services tag may exist in the Multiboot2 boot information structure).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct BasicMemoryInfoTag {
+    typ: TagTypeId,
+    size: u32,
+    memory_lower: u32,
+    memory_upper: u32,
+}
+
+unsafe impl TagTrait for BasicMemoryInfoTag {
+    const ID: TagType = TagType::BasicMeminfo;
+}
+
+impl BasicMemoryInfoTag {
+    /// Creates a new tag with the given amount of lower and upper memory in KiB.
+    pub fn new(memory_lower: u32, memory_upper: u32) -> Self {
+        Self {
+            typ: TagType::BasicMeminfo.into(),
+            size: size_of::<Self>() as u32,
+            memory_lower,
+            memory_upper,
+        }
+    }
+
+    /// Returns the amount of lower memory in KiB. Lower memory starts at
+    /// address 0 and is at most 640 KiB.
+    pub fn memory_lower(&self) -> u32 {
+        self.memory_lower
+    }
+
+    /// Returns the amount of upper memory in KiB. Upper memory starts at
+    /// address 1 MiB. The value is at most the address of the first upper
+    /// memory hole minus 1 MiB, but this is not guaranteed.
+    pub fn memory_upper(&self) -> u32 {
+        self.memory_upper
+    }
+}
 
 /// This tag provides an initial host memory map.
 ///
@@ -11,55 +76,124 @@ use core::marker::PhantomData;
 /// This tag may not be provided by some boot loaders on EFI platforms if EFI
 /// boot services are enabled and available for the loaded image (The EFI boot
 /// services tag may exist in the Multiboot2 boot information structure).
-#[derive(Debug)]
+///
+/// The memory areas follow the tag header directly and are not part of this
+/// struct, as the tag might hold no area at all.
 #[repr(C)]
 pub struct MemoryMapTag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     entry_size: u32,
     entry_version: u32,
-    first_area: MemoryArea,
+}
+
+unsafe impl TagTrait for MemoryMapTag {
+    const ID: TagType = TagType::Mmap;
+    const MIN_SIZE: usize = MEMORY_MAP_TAG_MIN_SIZE;
 }
 
 impl MemoryMapTag {
     /// Return an iterator over all AVAILABLE marked memory areas.
     pub fn memory_areas(&self) -> impl Iterator<Item = &MemoryArea> {
         self.all_memory_areas()
-            .filter(|entry| matches!(entry.typ, MemoryAreaType::Available))
+            .filter(|entry| entry.typ() == MemoryAreaType::Available)
     }
 
     /// Return an iterator over all marked memory areas.
-    pub fn all_memory_areas(&self) -> impl Iterator<Item = &MemoryArea> {
+    ///
+    /// Only areas that are completely within the tag are returned. If the
+    /// entry size is smaller than a [`MemoryArea`], no area is returned.
+    pub fn all_memory_areas(&self) -> MemoryAreaIter<'_> {
         let self_ptr = self as *const MemoryMapTag;
-        let start_area = (&self.first_area) as *const MemoryArea;
+        let start_area = unsafe { (self_ptr as *const u8).add(MEMORY_MAP_TAG_MIN_SIZE) };
+        let last_area = if self.entry_size as usize >= size_of::<MemoryArea>() {
+            (self_ptr as u64 + self.size as u64).saturating_sub(self.entry_size as u64)
+        } else {
+            0
+        };
         MemoryAreaIter {
             current_area: start_area as u64,
-            last_area: (self_ptr as u64 + (self.size - self.entry_size) as u64),
+            last_area,
             entry_size: self.entry_size,
             phantom: PhantomData,
         }
     }
+
+    /// Copies all memory areas into `buffer` and normalizes them: the areas
+    /// are sorted by their start address, empty areas are dropped, adjacent
+    /// and overlapping areas of the same type are merged and, where areas of
+    /// different types overlap, the more restrictive type wins. For example,
+    /// a reserved area that overlaps available memory is cut out of it.
+    ///
+    /// Returns the normalized areas, which are a prefix of `buffer`. Splitting
+    /// areas may need more entries than the tag has.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 64];
+    /// let areas = boot_info.memory_map_tag().unwrap().normalized_memory_areas(&mut buffer).unwrap();
+    /// ```
+    pub fn normalized_memory_areas<'b>(
+        &self,
+        buffer: &'b mut [MemoryArea],
+    ) -> Result<&'b [MemoryArea], MemoryMapBufferTooSmall> {
+        normalize(self.all_memory_areas().copied(), buffer)
+    }
+
+    /// Normalizes the memory map into `buffer`, keeps only the available
+    /// areas and removes the `reserved` ranges from them.
+    pub(crate) fn usable_memory_areas<'b>(
+        &self,
+        buffer: &'b mut [MemoryArea],
+        reserved: impl Iterator<Item = Range<u64>>,
+    ) -> Result<&'b [MemoryArea], MemoryMapBufferTooSmall> {
+        let mut len = self.normalized_memory_areas(buffer)?.len();
+        let mut i = 0;
+        while i < len {
+            if buffer[i].typ() == MemoryAreaType::Available {
+                i += 1;
+            } else {
+                remove(buffer, &mut len, i);
+            }
+        }
+        for range in reserved {
+            subtract(buffer, &mut len, range)?;
+        }
+        Ok(&buffer[..len])
+    }
 }
 
 /// A memory area entry descriptor.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C)]
 pub struct MemoryArea {
     base_addr: u64,
     length: u64,
-    typ: MemoryAreaType,
+    typ: u32,
     _reserved: u32,
 }
 
 impl MemoryArea {
+    /// Creates a new memory area entry descriptor.
+    pub fn new(base_addr: u64, length: u64, typ: MemoryAreaType) -> Self {
+        Self {
+            base_addr,
+            length,
+            typ: typ.into(),
+            _reserved: 0,
+        }
+    }
+
     /// The start address of the memory region.
     pub fn start_address(&self) -> u64 {
         self.base_addr
     }
 
-    /// The end address of the memory region.
+    /// The end address of the memory region. Saturates at `u64::MAX` for
+    /// corrupt areas that exceed the address space.
     pub fn end_address(&self) -> u64 {
-        self.base_addr + self.length
+        self.base_addr.saturating_add(self.length)
     }
 
     /// The size, in bytes, of the memory region.
@@ -69,6 +203,11 @@ impl MemoryArea {
 
     /// The type of the memory region.
     pub fn typ(&self) -> MemoryAreaType {
+        self.typ.into()
+    }
+
+    /// The raw type of the memory region, as the bootloader reported it.
+    pub fn typ_raw(&self) -> u32 {
         self.typ
     }
 }
@@ -76,29 +215,120 @@ impl MemoryArea {
 /// An enum of possible reported region types.
 /// Inside the Multiboot2 spec this is kind of hidden
 /// inside the implementation of `struct multiboot_mmap_entry`.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-#[repr(u32)]
+///
+/// The values are the address range types of the ACPI specification (E820).
+/// Types that have no variant, such as OEM-defined types, are represented by
+/// [`MemoryAreaType::Custom`]. Use `u32::from` and `MemoryAreaType::from` to
+/// convert between the enum and raw values.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub enum MemoryAreaType {
     /// Available memory free to be used by the OS.
-    Available = 1,
+    Available,
 
     /// A reserved area that must not be used.
-    Reserved = 2,
+    Reserved,
 
     /// Usable memory holding ACPI information.
-    AcpiAvailable = 3,
+    AcpiAvailable,
 
     /// Reserved memory which needs to be preserved on hibernation.
     /// Also called NVS in spec, which stands for "Non-Volatile Sleep/Storage",
     /// which is part of ACPI specification.
-    ReservedHibernate = 4,
+    ReservedHibernate,
 
     /// Memory which is occupied by defective RAM modules.
-    Defective = 5,
+    Defective,
+
+    /// Memory that is disabled and must not be used, e.g. because it is not
+    /// enabled by the firmware.
+    Disabled,
+
+    /// Persistent (non-volatile) memory, which must be used as storage
+    /// rather than as normal RAM.
+    PersistentMemory,
+
+    /// A type that has no variant in this enum, for example an OEM-defined
+    /// type. Holds the raw value.
+    Custom(u32),
+}
+
+impl From<u32> for MemoryAreaType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => MemoryAreaType::Available,
+            2 => MemoryAreaType::Reserved,
+            3 => MemoryAreaType::AcpiAvailable,
+            4 => MemoryAreaType::ReservedHibernate,
+            5 => MemoryAreaType::Defective,
+            6 => MemoryAreaType::Disabled,
+            7 => MemoryAreaType::PersistentMemory,
+            value => MemoryAreaType::Custom(value),
+        }
+    }
+}
+
+impl From<MemoryAreaType> for u32 {
+    fn from(value: MemoryAreaType) -> Self {
+        match value {
+            MemoryAreaType::Available => 1,
+            MemoryAreaType::Reserved => 2,
+            MemoryAreaType::AcpiAvailable => 3,
+            MemoryAreaType::ReservedHibernate => 4,
+            MemoryAreaType::Defective => 5,
+            MemoryAreaType::Disabled => 6,
+            MemoryAreaType::PersistentMemory => 7,
+            MemoryAreaType::Custom(value) => value,
+        }
+    }
+}
+
+// Equality and hashing are implemented by the numeric value, so that
+// `MemoryAreaType::Custom(1)` and `MemoryAreaType::Available` are considered
+// the same.
+
+impl PartialEq for MemoryAreaType {
+    fn eq(&self, other: &MemoryAreaType) -> bool {
+        u32::from(*self) == u32::from(*other)
+    }
+}
+
+impl Eq for MemoryAreaType {}
+
+impl Hash for MemoryAreaType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u32::from(*self).hash(state)
+    }
+}
+
+// each compare/equal direction must be implemented manually
+impl PartialEq<u32> for MemoryAreaType {
+    fn eq(&self, other: &u32) -> bool {
+        u32::from(*self) == *other
+    }
+}
+
+// each compare/equal direction must be implemented manually
+impl PartialEq<MemoryAreaType> for u32 {
+    fn eq(&self, other: &MemoryAreaType) -> bool {
+        *self == u32::from(*other)
+    }
+}
+
+impl Debug for MemoryMapTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MemoryMapTag")
+            .field("typ", &{ self.typ })
+            .field("size", &{ self.size })
+            .field("entry_size", &{ self.entry_size })
+            .field("entry_version", &{ self.entry_version })
+            .field("areas", &self.all_memory_areas())
+            .finish()
+    }
 }
 
 /// An iterator over all memory areas
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct MemoryAreaIter<'a> {
     current_area: u64,
     last_area: u64,
@@ -119,15 +349,31 @@ impl<'a> Iterator for MemoryAreaIter<'a> {
     }
 }
 
+impl Debug for MemoryAreaIter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|area| {
+            list.entry(area);
+        });
+        list.finish()
+    }
+}
+
 /// EFI memory map as per EFI specification.
-#[derive(Debug)]
+///
+/// The descriptors follow the tag header directly and are not part of this
+/// struct, as the tag might hold no descriptor at all.
 #[repr(C)]
 pub struct EFIMemoryMapTag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     desc_size: u32,
     desc_version: u32,
-    first_desc: EFIMemoryDesc,
+}
+
+unsafe impl TagTrait for EFIMemoryMapTag {
+    const ID: TagType = TagType::EfiMmap;
+    const MIN_SIZE: usize = EFI_MEMORY_MAP_TAG_MIN_SIZE;
 }
 
 impl EFIMemoryMapTag {
@@ -135,20 +381,55 @@ impl EFIMemoryMapTag {
     ///
     /// This differs from `MemoryMapTag` as for UEFI, the OS needs some non-
     /// available memory areas for tables and such.
-    pub fn memory_areas(&self) -> EFIMemoryAreaIter {
-        let self_ptr = self as *const EFIMemoryMapTag;
-        let start_area = (&self.first_desc) as *const EFIMemoryDesc;
+    ///
+    /// The iterator returns exactly the `(size - 16) / desc_size` descriptors
+    /// that are completely within the tag. If the descriptor size is smaller
+    /// than an [`EFIMemoryDesc`], no descriptor is returned.
+    pub fn memory_areas(&self) -> EFIMemoryAreaIter<'_> {
+        let start_area = unsafe {
+            (self as *const EFIMemoryMapTag as *const u8).add(EFI_MEMORY_MAP_TAG_MIN_SIZE)
+        };
+        let remaining = if self.desc_size as usize >= size_of::<EFIMemoryDesc>() {
+            (self.size as usize).saturating_sub(EFI_MEMORY_MAP_TAG_MIN_SIZE)
+                / self.desc_size as usize
+        } else {
+            0
+        };
         EFIMemoryAreaIter {
             current_area: start_area as u64,
-            last_area: (self_ptr as u64 + self.size as u64),
+            remaining,
             entry_size: self.desc_size,
             phantom: PhantomData,
         }
     }
+
+    /// The size of each descriptor in bytes. This may be larger than an
+    /// [`EFIMemoryDesc`], as newer versions of UEFI can extend the descriptor.
+    pub fn desc_size(&self) -> u32 {
+        self.desc_size
+    }
+
+    /// The version of the descriptors. Version 1 is the only one that the
+    /// UEFI specification defines so far.
+    pub fn desc_version(&self) -> u32 {
+        self.desc_version
+    }
+
+    /// Copies all descriptors into `buffer` and normalizes them like
+    /// [`MemoryMapTag::normalized_memory_areas`]. Descriptors are merged only
+    /// if both the type and the attributes are equal.
+    ///
+    /// Returns the normalized descriptors, which are a prefix of `buffer`.
+    pub fn normalized_memory_areas<'b>(
+        &self,
+        buffer: &'b mut [EFIMemoryDesc],
+    ) -> Result<&'b [EFIMemoryDesc], MemoryMapBufferTooSmall> {
+        normalize(self.memory_areas().copied(), buffer)
+    }
 }
 
 /// EFI Boot Memory Map Descriptor
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C)]
 pub struct EFIMemoryDesc {
     typ: u32,
@@ -160,7 +441,10 @@ pub struct EFIMemoryDesc {
 }
 
 /// An enum of possible reported region types.
-#[derive(Debug, PartialEq, Eq)]
+///
+/// Use `u32::from` and `EFIMemoryAreaType::from` to convert between the enum
+/// and the raw UEFI memory types.
+#[derive(Debug, Clone, Copy)]
 pub enum EFIMemoryAreaType {
     /// Unusable.
     EfiReservedMemoryType,
@@ -209,11 +493,94 @@ pub enum EFIMemoryAreaType {
     EfiPalCode,
     /// Available memory supporting byte-addressable non-volatility.
     EfiPersistentMemory,
-    /// Unknown region type, treat as unusable.
-    EfiUnknown,
+    /// Memory that must be accepted by the boot target before it can be
+    /// used, e.g. in confidential computing environments (UEFI 2.9+).
+    EfiUnacceptedMemoryType,
+    /// Unknown region type, treat as unusable. Holds the raw value, which may
+    /// be an OEM- or OS-specific type.
+    EfiUnknown(u32),
+}
+
+impl From<u32> for EFIMemoryAreaType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => EFIMemoryAreaType::EfiReservedMemoryType,
+            1 => EFIMemoryAreaType::EfiLoaderCode,
+            2 => EFIMemoryAreaType::EfiLoaderData,
+            3 => EFIMemoryAreaType::EfiBootServicesCode,
+            4 => EFIMemoryAreaType::EfiBootServicesData,
+            5 => EFIMemoryAreaType::EfiRuntimeServicesCode,
+            6 => EFIMemoryAreaType::EfiRuntimeServicesData,
+            7 => EFIMemoryAreaType::EfiConventionalMemory,
+            8 => EFIMemoryAreaType::EfiUnusableMemory,
+            9 => EFIMemoryAreaType::EfiACPIReclaimMemory,
+            10 => EFIMemoryAreaType::EfiACPIMemoryNVS,
+            11 => EFIMemoryAreaType::EfiMemoryMappedIO,
+            12 => EFIMemoryAreaType::EfiMemoryMappedIOPortSpace,
+            13 => EFIMemoryAreaType::EfiPalCode,
+            14 => EFIMemoryAreaType::EfiPersistentMemory,
+            15 => EFIMemoryAreaType::EfiUnacceptedMemoryType,
+            value => EFIMemoryAreaType::EfiUnknown(value),
+        }
+    }
+}
+
+impl From<EFIMemoryAreaType> for u32 {
+    fn from(value: EFIMemoryAreaType) -> Self {
+        match value {
+            EFIMemoryAreaType::EfiReservedMemoryType => 0,
+            EFIMemoryAreaType::EfiLoaderCode => 1,
+            EFIMemoryAreaType::EfiLoaderData => 2,
+            EFIMemoryAreaType::EfiBootServicesCode => 3,
+            EFIMemoryAreaType::EfiBootServicesData => 4,
+            EFIMemoryAreaType::EfiRuntimeServicesCode => 5,
+            EFIMemoryAreaType::EfiRuntimeServicesData => 6,
+            EFIMemoryAreaType::EfiConventionalMemory => 7,
+            EFIMemoryAreaType::EfiUnusableMemory => 8,
+            EFIMemoryAreaType::EfiACPIReclaimMemory => 9,
+            EFIMemoryAreaType::EfiACPIMemoryNVS => 10,
+            EFIMemoryAreaType::EfiMemoryMappedIO => 11,
+            EFIMemoryAreaType::EfiMemoryMappedIOPortSpace => 12,
+            EFIMemoryAreaType::EfiPalCode => 13,
+            EFIMemoryAreaType::EfiPersistentMemory => 14,
+            EFIMemoryAreaType::EfiUnacceptedMemoryType => 15,
+            EFIMemoryAreaType::EfiUnknown(value) => value,
+        }
+    }
+}
+
+// Equality and hashing are implemented by the numeric value, so that
+// `EFIMemoryAreaType::EfiUnknown(7)` and
+// `EFIMemoryAreaType::EfiConventionalMemory` are considered the same.
+
+impl PartialEq for EFIMemoryAreaType {
+    fn eq(&self, other: &EFIMemoryAreaType) -> bool {
+        u32::from(*self) == u32::from(*other)
+    }
+}
+
+impl Eq for EFIMemoryAreaType {}
+
+impl Hash for EFIMemoryAreaType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u32::from(*self).hash(state)
+    }
 }
 
 impl EFIMemoryDesc {
+    /// Creates a new EFI memory descriptor from the raw UEFI memory type,
+    /// the addresses, the number of 4KiB pages and the raw attribute bits.
+    pub fn new(typ: u32, phys_addr: u64, virt_addr: u64, num_pages: u64, attr: u64) -> Self {
+        Self {
+            typ,
+            _padding: 0,
+            phys_addr,
+            virt_addr,
+            num_pages,
+            attr,
+        }
+    }
+
     /// The physical address of the memory region.
     pub fn physical_address(&self) -> u64 {
         self.phys_addr
@@ -224,48 +591,479 @@ impl EFIMemoryDesc {
         self.virt_addr
     }
 
-    /// The size in bytes of the memory region.
+    /// The size in bytes of the memory region. Saturates at `u64::MAX` for
+    /// page counts that the address space cannot hold.
     pub fn size(&self) -> u64 {
         // Spec says this is number of 4KiB pages.
-        self.num_pages * 4096
+        self.num_pages.saturating_mul(4096)
+    }
+
+    /// The number of 4KiB pages of the memory region.
+    pub fn page_count(&self) -> u64 {
+        self.num_pages
     }
 
     /// The type of the memory region.
     pub fn typ(&self) -> EFIMemoryAreaType {
-        match self.typ {
-            0 => EFIMemoryAreaType::EfiReservedMemoryType,
-            1 => EFIMemoryAreaType::EfiLoaderCode,
-            2 => EFIMemoryAreaType::EfiLoaderData,
-            3 => EFIMemoryAreaType::EfiBootServicesCode,
-            4 => EFIMemoryAreaType::EfiBootServicesData,
-            5 => EFIMemoryAreaType::EfiRuntimeServicesCode,
-            6 => EFIMemoryAreaType::EfiRuntimeServicesData,
-            7 => EFIMemoryAreaType::EfiConventionalMemory,
-            8 => EFIMemoryAreaType::EfiUnusableMemory,
-            9 => EFIMemoryAreaType::EfiACPIReclaimMemory,
-            10 => EFIMemoryAreaType::EfiACPIMemoryNVS,
-            11 => EFIMemoryAreaType::EfiMemoryMappedIO,
-            12 => EFIMemoryAreaType::EfiMemoryMappedIOPortSpace,
-            13 => EFIMemoryAreaType::EfiPalCode,
-            14 => EFIMemoryAreaType::EfiPersistentMemory,
-            _ => EFIMemoryAreaType::EfiUnknown,
+        self.typ.into()
+    }
+
+    /// The attributes of the memory region, i.e. its capabilities. Unknown
+    /// bits are dropped.
+    pub fn attributes(&self) -> EFIMemoryAttribute {
+        EFIMemoryAttribute::from_bits_truncate(self.attr)
+    }
+
+    /// The raw attributes of the memory region.
+    pub fn attributes_raw(&self) -> u64 {
+        self.attr
+    }
+}
+
+bitflags! {
+    /// The attributes of an [`EFIMemoryDesc`], which describe the
+    /// capabilities of the memory region. The names of the UEFI specification
+    /// are given in parentheses.
+    pub struct EFIMemoryAttribute: u64 {
+        /// The memory supports being configured as not cacheable (UC).
+        const UNCACHEABLE = 0x1;
+
+        /// The memory supports being configured as write combining (WC).
+        const WRITE_COMBINE = 0x2;
+
+        /// The memory supports being configured as cacheable with a write
+        /// through policy (WT).
+        const WRITE_THROUGH = 0x4;
+
+        /// The memory supports being configured as cacheable with a write
+        /// back policy (WB).
+        const WRITE_BACK = 0x8;
+
+        /// The memory supports being configured as not cacheable, exported
+        /// and supporting the "fetch and add" semaphore mechanism (UCE).
+        const UNCACHEABLE_EXPORTED = 0x10;
+
+        /// The memory supports being configured as write-protected (WP).
+        const WRITE_PROTECT = 0x1000;
+
+        /// The memory supports being configured as read-protected (RP).
+        const READ_PROTECT = 0x2000;
+
+        /// The memory supports being configured so it is protected from
+        /// executing code (XP).
+        const EXECUTE_PROTECT = 0x4000;
+
+        /// The memory is persistent (NV).
+        const NON_VOLATILE = 0x8000;
+
+        /// The memory has a higher reliability than other memory.
+        const MORE_RELIABLE = 0x10000;
+
+        /// The memory supports being configured as read-only (RO).
+        const READ_ONLY = 0x20000;
+
+        /// The memory is earmarked for specific purposes, such as specific
+        /// device drivers or applications (SP).
+        const SPECIFIC_PURPOSE = 0x40000;
+
+        /// The memory is capable of being protected with the CPU's memory
+        /// cryptographic capabilities.
+        const CPU_CRYPTO = 0x80000;
+
+        /// The memory must be given a virtual mapping by the operating system
+        /// when `SetVirtualAddressMap()` is called, as it is used by the
+        /// runtime services.
+        const RUNTIME = 0x8000_0000_0000_0000;
+    }
+}
+
+/// A memory region of either the EFI memory map or the legacy memory map,
+/// classified by a common [`MemoryRegionKind`].
+///
+/// The regions are returned by [`crate::BootInformation::memory_regions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    start: u64,
+    size: u64,
+    kind: MemoryRegionKind,
+}
+
+impl MemoryRegion {
+    /// Creates a new memory region.
+    pub fn new(start: u64, size: u64, kind: MemoryRegionKind) -> Self {
+        Self { start, size, kind }
+    }
+
+    /// The start address of the memory region.
+    pub fn start_address(&self) -> u64 {
+        self.start
+    }
+
+    /// The end address of the memory region.
+    pub fn end_address(&self) -> u64 {
+        self.start.saturating_add(self.size)
+    }
+
+    /// The size, in bytes, of the memory region.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The kind of the memory region.
+    pub fn kind(&self) -> MemoryRegionKind {
+        self.kind
+    }
+}
+
+impl From<&MemoryArea> for MemoryRegion {
+    fn from(area: &MemoryArea) -> Self {
+        let kind = match area.typ() {
+            MemoryAreaType::Available => MemoryRegionKind::Usable,
+            MemoryAreaType::AcpiAvailable => MemoryRegionKind::Reclaimable,
+            MemoryAreaType::ReservedHibernate | MemoryAreaType::PersistentMemory => {
+                MemoryRegionKind::Preserved
+            }
+            MemoryAreaType::Reserved | MemoryAreaType::Disabled | MemoryAreaType::Custom(_) => {
+                MemoryRegionKind::Reserved
+            }
+            MemoryAreaType::Defective => MemoryRegionKind::Defective,
+        };
+        Self::new(area.start_address(), area.size(), kind)
+    }
+}
+
+impl From<&EFIMemoryDesc> for MemoryRegion {
+    fn from(desc: &EFIMemoryDesc) -> Self {
+        let kind = match desc.typ() {
+            EFIMemoryAreaType::EfiConventionalMemory => MemoryRegionKind::Usable,
+            EFIMemoryAreaType::EfiLoaderCode
+            | EFIMemoryAreaType::EfiLoaderData
+            | EFIMemoryAreaType::EfiBootServicesCode
+            | EFIMemoryAreaType::EfiBootServicesData
+            | EFIMemoryAreaType::EfiACPIReclaimMemory => MemoryRegionKind::Reclaimable,
+            EFIMemoryAreaType::EfiRuntimeServicesCode
+            | EFIMemoryAreaType::EfiRuntimeServicesData
+            | EFIMemoryAreaType::EfiACPIMemoryNVS
+            | EFIMemoryAreaType::EfiPalCode
+            | EFIMemoryAreaType::EfiPersistentMemory => MemoryRegionKind::Preserved,
+            EFIMemoryAreaType::EfiReservedMemoryType
+            | EFIMemoryAreaType::EfiMemoryMappedIO
+            | EFIMemoryAreaType::EfiMemoryMappedIOPortSpace
+            | EFIMemoryAreaType::EfiUnacceptedMemoryType
+            | EFIMemoryAreaType::EfiUnknown(_) => MemoryRegionKind::Reserved,
+            EFIMemoryAreaType::EfiUnusableMemory => MemoryRegionKind::Defective,
+        };
+        Self::new(desc.physical_address(), desc.size(), kind)
+    }
+}
+
+/// The kind of a [`MemoryRegion`], which is derived from the type of the
+/// memory area of the legacy memory map or of the EFI memory descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemoryRegionKind {
+    /// Memory that is free to be used by the OS.
+    Usable,
+    /// Memory that can be used by the OS after it doesn't need the content
+    /// anymore: the ACPI tables, the EFI boot services and the data of the
+    /// EFI loader. Note that the kernel, the boot information and the modules
+    /// are usually in EFI loader data.
+    Reclaimable,
+    /// Memory whose content must be preserved, such as the EFI runtime
+    /// services, the ACPI NVS memory and persistent memory.
+    Preserved,
+    /// Memory that is reserved by the firmware or used for memory-mapped IO.
+    Reserved,
+    /// Memory that is defective and must not be used.
+    Defective,
+}
+
+/// An iterator over the [`MemoryRegion`]s of either the EFI memory map or the
+/// legacy memory map.
+#[derive(Clone, Debug)]
+pub struct MemoryRegionIter<'a> {
+    inner: MemoryRegionIterInner<'a>,
+}
+
+#[derive(Clone, Debug)]
+enum MemoryRegionIterInner<'a> {
+    Efi(EFIMemoryAreaIter<'a>),
+    Legacy(MemoryAreaIter<'a>),
+    Empty,
+}
+
+/// Returns an iterator over the regions of the EFI memory map, if it has at
+/// least one descriptor, or otherwise over the legacy memory map.
+pub(crate) fn memory_region_iter<'a>(
+    efi_memory_map: Option<&'a EFIMemoryMapTag>,
+    memory_map: Option<&'a MemoryMapTag>,
+) -> MemoryRegionIter<'a> {
+    let efi_areas = efi_memory_map
+        .map(|tag| tag.memory_areas())
+        .filter(|areas| areas.len() != 0);
+    let inner = match (efi_areas, memory_map) {
+        (Some(areas), _) => MemoryRegionIterInner::Efi(areas),
+        (None, Some(tag)) => MemoryRegionIterInner::Legacy(tag.all_memory_areas()),
+        (None, None) => MemoryRegionIterInner::Empty,
+    };
+    MemoryRegionIter { inner }
+}
+
+impl Iterator for MemoryRegionIter<'_> {
+    type Item = MemoryRegion;
+
+    fn next(&mut self) -> Option<MemoryRegion> {
+        match &mut self.inner {
+            MemoryRegionIterInner::Efi(areas) => areas.next().map(MemoryRegion::from),
+            MemoryRegionIterInner::Legacy(areas) => areas.next().map(MemoryRegion::from),
+            MemoryRegionIterInner::Empty => None,
         }
     }
 }
 
-/// EFI ExitBootServices was not called
-#[derive(Debug)]
+/// The buffer that was passed to [`MemoryMapTag::normalized_memory_areas`] or
+/// [`EFIMemoryMapTag::normalized_memory_areas`] is too small for the memory
+/// map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryMapBufferTooSmall;
+
+/// A memory map entry that can be normalized by [`normalize`].
+trait NormalizeArea: Copy {
+    fn start(&self) -> u64;
+
+    fn end(&self) -> u64;
+
+    /// Returns how restrictive the type of the area is. If areas overlap, the
+    /// one with the higher value wins.
+    fn restrictiveness(&self) -> u8;
+
+    /// Checks if two areas can be merged into one.
+    fn same_kind(&self, other: &Self) -> bool;
+
+    /// Returns a copy of the area that covers `start..end` instead.
+    fn with_range(&self, start: u64, end: u64) -> Self;
+}
+
+impl NormalizeArea for MemoryArea {
+    fn start(&self) -> u64 {
+        self.base_addr
+    }
+
+    fn end(&self) -> u64 {
+        self.base_addr.saturating_add(self.length)
+    }
+
+    fn restrictiveness(&self) -> u8 {
+        match self.typ() {
+            MemoryAreaType::Available => 0,
+            MemoryAreaType::AcpiAvailable => 1,
+            MemoryAreaType::ReservedHibernate | MemoryAreaType::PersistentMemory => 2,
+            MemoryAreaType::Reserved | MemoryAreaType::Disabled | MemoryAreaType::Custom(_) => 3,
+            MemoryAreaType::Defective => 4,
+        }
+    }
+
+    fn same_kind(&self, other: &Self) -> bool {
+        self.typ == other.typ
+    }
+
+    fn with_range(&self, start: u64, end: u64) -> Self {
+        Self::new(start, end - start, self.typ())
+    }
+}
+
+impl NormalizeArea for EFIMemoryDesc {
+    fn start(&self) -> u64 {
+        self.phys_addr
+    }
+
+    fn end(&self) -> u64 {
+        self.phys_addr
+            .saturating_add(self.num_pages.saturating_mul(4096))
+    }
+
+    fn restrictiveness(&self) -> u8 {
+        match self.typ() {
+            EFIMemoryAreaType::EfiConventionalMemory => 0,
+            EFIMemoryAreaType::EfiBootServicesCode | EFIMemoryAreaType::EfiBootServicesData => 1,
+            EFIMemoryAreaType::EfiLoaderCode | EFIMemoryAreaType::EfiLoaderData => 2,
+            EFIMemoryAreaType::EfiACPIReclaimMemory => 3,
+            EFIMemoryAreaType::EfiPersistentMemory => 4,
+            EFIMemoryAreaType::EfiRuntimeServicesCode
+            | EFIMemoryAreaType::EfiRuntimeServicesData => 5,
+            EFIMemoryAreaType::EfiACPIMemoryNVS => 6,
+            EFIMemoryAreaType::EfiMemoryMappedIO
+            | EFIMemoryAreaType::EfiMemoryMappedIOPortSpace
+            | EFIMemoryAreaType::EfiPalCode
+            | EFIMemoryAreaType::EfiReservedMemoryType
+            | EFIMemoryAreaType::EfiUnacceptedMemoryType
+            | EFIMemoryAreaType::EfiUnknown(_) => 7,
+            EFIMemoryAreaType::EfiUnusableMemory => 8,
+        }
+    }
+
+    fn same_kind(&self, other: &Self) -> bool {
+        self.typ == other.typ && self.attr == other.attr
+    }
+
+    fn with_range(&self, start: u64, end: u64) -> Self {
+        // the virtual address is only set for memory that the firmware mapped
+        let virt_addr = match self.virt_addr {
+            0 => 0,
+            virt_addr => virt_addr.wrapping_add(start.wrapping_sub(self.phys_addr)),
+        };
+        Self::new(self.typ, start, virt_addr, (end - start) / 4096, self.attr)
+    }
+}
+
+/// Copies `areas` into `buffer`, sorts them, drops empty areas, merges areas
+/// of the same kind and resolves overlaps in favor of the more restrictive
+/// area. Returns the normalized prefix of `buffer`.
+fn normalize<T: NormalizeArea>(
+    areas: impl Iterator<Item = T>,
+    buffer: &mut [T],
+) -> Result<&[T], MemoryMapBufferTooSmall> {
+    let mut len = 0;
+    for area in areas.filter(|area| area.start() < area.end()) {
+        *buffer.get_mut(len).ok_or(MemoryMapBufferTooSmall)? = area;
+        len += 1;
+    }
+
+    // Each step removes an area or an overlap. If an area is moved, the order
+    // might break, so the areas are sorted again.
+    let mut sorted = false;
+    while !sorted {
+        buffer[..len].sort_unstable_by_key(|area| (area.start(), area.end()));
+        sorted = true;
+        let mut i = 0;
+        while i + 1 < len {
+            let (a, b) = (buffer[i], buffer[i + 1]);
+            if b.start() > a.end() || (b.start() == a.end() && !a.same_kind(&b)) {
+                i += 1;
+            } else if a.same_kind(&b) {
+                buffer[i] = a.with_range(a.start(), a.end().max(b.end()));
+                remove(buffer, &mut len, i + 1);
+            } else if a.restrictiveness() >= b.restrictiveness() {
+                // cut the overlap out of b
+                if b.end() <= a.end() {
+                    remove(buffer, &mut len, i + 1);
+                } else {
+                    buffer[i + 1] = b.with_range(a.end(), b.end());
+                    sorted = false;
+                    break;
+                }
+            } else {
+                // cut the overlap out of a, which might split it in two
+                if a.end() > b.end() {
+                    *buffer.get_mut(len).ok_or(MemoryMapBufferTooSmall)? =
+                        a.with_range(b.end(), a.end());
+                    len += 1;
+                    sorted = false;
+                }
+                if a.start() == b.start() {
+                    remove(buffer, &mut len, i);
+                } else {
+                    buffer[i] = a.with_range(a.start(), b.start());
+                }
+                if !sorted {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(&buffer[..len])
+}
+
+/// Removes `range` from the first `len` areas of `buffer`. Areas that contain
+/// the range are split in two.
+fn subtract<T: NormalizeArea>(
+    buffer: &mut [T],
+    len: &mut usize,
+    range: Range<u64>,
+) -> Result<(), MemoryMapBufferTooSmall> {
+    let mut i = 0;
+    while i < *len {
+        let area = buffer[i];
+        if range.start >= range.end || range.end <= area.start() || area.end() <= range.start {
+            i += 1;
+            continue;
+        }
+        match (area.start() < range.start, range.end < area.end()) {
+            (false, false) => remove(buffer, len, i),
+            (true, false) => {
+                buffer[i] = area.with_range(area.start(), range.start);
+                i += 1;
+            }
+            (false, true) => {
+                buffer[i] = area.with_range(range.end, area.end());
+                i += 1;
+            }
+            (true, true) => {
+                if *len == buffer.len() {
+                    return Err(MemoryMapBufferTooSmall);
+                }
+                buffer.copy_within(i + 1..*len, i + 2);
+                buffer[i] = area.with_range(area.start(), range.start);
+                buffer[i + 1] = area.with_range(range.end, area.end());
+                *len += 1;
+                i += 2;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removes the area at `index` from the first `len` areas of `buffer`.
+fn remove<T: Copy>(buffer: &mut [T], len: &mut usize, index: usize) {
+    buffer.copy_within(index + 1..*len, index);
+    *len -= 1;
+}
+
+/// This tag indicates that the bootloader did not call ExitBootServices, i.e.
+/// the EFI boot services are still available to the loaded image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub struct EFIBootServicesNotExited {
-    typ: u32,
+    typ: TagTypeId,
     size: u32,
 }
 
+unsafe impl TagTrait for EFIBootServicesNotExited {
+    const ID: TagType = TagType::EfiBs;
+}
+
+impl EFIBootServicesNotExited {
+    /// Creates a new tag.
+    pub fn new() -> Self {
+        Self {
+            typ: TagType::EfiBs.into(),
+            size: size_of::<Self>() as u32,
+        }
+    }
+}
+
+impl Default for EFIBootServicesNotExited {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for EFIMemoryMapTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EFIMemoryMapTag")
+            .field("typ", &{ self.typ })
+            .field("size", &{ self.size })
+            .field("desc_size", &{ self.desc_size })
+            .field("desc_version", &{ self.desc_version })
+            .field("descs", &self.memory_areas())
+            .finish()
+    }
+}
+
 /// An iterator over ALL EFI memory areas.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct EFIMemoryAreaIter<'a> {
     current_area: u64,
-    last_area: u64,
+    remaining: usize,
     entry_size: u32,
     phantom: PhantomData<&'a EFIMemoryDesc>,
 }
@@ -273,12 +1071,269 @@ pub struct EFIMemoryAreaIter<'a> {
 impl<'a> Iterator for EFIMemoryAreaIter<'a> {
     type Item = &'a EFIMemoryDesc;
     fn next(&mut self) -> Option<&'a EFIMemoryDesc> {
-        if self.current_area > self.last_area {
+        if self.remaining == 0 {
             None
         } else {
             let area = unsafe { &*(self.current_area as *const EFIMemoryDesc) };
             self.current_area += self.entry_size as u64;
+            self.remaining -= 1;
             Some(area)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for EFIMemoryAreaIter<'_> {}
+
+impl Debug for EFIMemoryAreaIter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|desc| {
+            list.entry(desc);
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EFIMemoryAreaType, EFIMemoryAttribute, EFIMemoryDesc, EFIMemoryMapTag, MemoryArea,
+        MemoryAreaType, MemoryMapBufferTooSmall, MemoryMapTag,
+    };
+    use crate::TagType;
+
+    /// Returns the tag with the given entries in native endian format, with
+    /// the alignment of a tag.
+    fn get_tag(typ: TagType, entry_size: u32, version: u32, entries: &[u8]) -> std::vec::Vec<u64> {
+        let mut bytes = std::vec::Vec::new();
+        bytes.extend(&u32::from(typ).to_ne_bytes());
+        bytes.extend(&(16 + entries.len() as u32).to_ne_bytes());
+        bytes.extend(&entry_size.to_ne_bytes());
+        bytes.extend(&version.to_ne_bytes());
+        bytes.extend(entries);
+        bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_ne_bytes(word)
+            })
+            .collect()
+    }
+
+    fn area_bytes(area: &MemoryArea) -> [u8; 24] {
+        unsafe { core::mem::transmute(*area) }
+    }
+
+    fn desc_bytes(desc: &EFIMemoryDesc) -> [u8; 40] {
+        unsafe { core::mem::transmute(*desc) }
+    }
+
+    #[test]
+    fn test_normalize_memory_map() {
+        let areas = [
+            MemoryArea::new(0x10_0000, 0x7ff0_0000, MemoryAreaType::Available),
+            MemoryArea::new(0, 0x9_fc00, MemoryAreaType::Available),
+            // overlaps the available memory at the start
+            MemoryArea::new(0x9_f000, 0x6_1000, MemoryAreaType::Reserved),
+            // within the available memory
+            MemoryArea::new(0x7000_0000, 0x1000, MemoryAreaType::AcpiAvailable),
+            // adjacent to the previous area of the same type
+            MemoryArea::new(0x7000_1000, 0x1000, MemoryAreaType::AcpiAvailable),
+            // empty
+            MemoryArea::new(0x8000_0000, 0, MemoryAreaType::Defective),
+            // overlaps reserved memory, which is more restrictive
+            MemoryArea::new(0xf_0000, 0x1_0000, MemoryAreaType::Available),
+        ];
+        let entries: std::vec::Vec<u8> = areas.iter().flat_map(area_bytes).collect();
+        let tag = get_tag(TagType::Mmap, 24, 0, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<MemoryMapTag>().as_ref().unwrap() };
+
+        let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 8];
+        assert_eq!(
+            tag.normalized_memory_areas(&mut buffer).unwrap(),
+            [
+                MemoryArea::new(0, 0x9_f000, MemoryAreaType::Available),
+                MemoryArea::new(0x9_f000, 0x6_1000, MemoryAreaType::Reserved),
+                MemoryArea::new(0x10_0000, 0x6ff0_0000, MemoryAreaType::Available),
+                MemoryArea::new(0x7000_0000, 0x2000, MemoryAreaType::AcpiAvailable),
+                MemoryArea::new(0x7000_2000, 0xfff_e000, MemoryAreaType::Available),
+            ]
+        );
+
+        // splitting the available memory needs one more entry than the tag has
+        let entries: std::vec::Vec<u8> = areas[..4].iter().flat_map(area_bytes).collect();
+        let tag = get_tag(TagType::Mmap, 24, 0, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<MemoryMapTag>().as_ref().unwrap() };
+        let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 4];
+        assert_eq!(
+            tag.normalized_memory_areas(&mut buffer),
+            Err(MemoryMapBufferTooSmall)
+        );
+        let mut buffer = [MemoryArea::new(0, 0, MemoryAreaType::Reserved); 5];
+        assert_eq!(tag.normalized_memory_areas(&mut buffer).unwrap().len(), 5);
+    }
+
+    /// Types that the enum doesn't know must be preserved.
+    #[test]
+    fn test_area_types() {
+        let mut entries = std::vec::Vec::new();
+        for &typ in &[1_u32, 7, 12, 0xf000_0001] {
+            entries.extend(&0x10_0000_u64.to_ne_bytes());
+            entries.extend(&0x1000_u64.to_ne_bytes());
+            entries.extend(&typ.to_ne_bytes());
+            entries.extend(&0_u32.to_ne_bytes());
+        }
+        let tag = get_tag(TagType::Mmap, 24, 0, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<MemoryMapTag>().as_ref().unwrap() };
+        let types: std::vec::Vec<MemoryAreaType> =
+            tag.all_memory_areas().map(|area| area.typ()).collect();
+        assert_eq!(
+            types,
+            [
+                MemoryAreaType::Available,
+                MemoryAreaType::PersistentMemory,
+                MemoryAreaType::Custom(12),
+                MemoryAreaType::Custom(0xf000_0001),
+            ]
+        );
+        assert_eq!(tag.all_memory_areas().nth(2).unwrap().typ_raw(), 12);
+        assert_eq!(tag.memory_areas().count(), 1);
+        assert_eq!(MemoryAreaType::Custom(1), MemoryAreaType::Available);
+        assert_eq!(MemoryAreaType::from(6), MemoryAreaType::Disabled);
+        assert_eq!(u32::from(MemoryAreaType::Defective), 5);
+    }
+
+    #[test]
+    fn test_efi_memory_desc() {
+        let desc = EFIMemoryDesc::new(
+            5,
+            0x7f00_0000,
+            0xffff_8000_7f00_0000,
+            3,
+            0x8000_0000_0000_400f,
+        );
+        assert_eq!(desc.typ(), EFIMemoryAreaType::EfiRuntimeServicesCode);
+        assert_eq!(desc.page_count(), 3);
+        assert_eq!(desc.size(), 0x3000);
+        assert_eq!(
+            desc.attributes(),
+            EFIMemoryAttribute::RUNTIME
+                | EFIMemoryAttribute::EXECUTE_PROTECT
+                | EFIMemoryAttribute::WRITE_BACK
+                | EFIMemoryAttribute::WRITE_THROUGH
+                | EFIMemoryAttribute::WRITE_COMBINE
+                | EFIMemoryAttribute::UNCACHEABLE
+        );
+        assert_eq!(desc.attributes_raw(), 0x8000_0000_0000_400f);
+        let huge = EFIMemoryDesc::new(7, 0, 0, u64::MAX / 2, 0);
+        assert_eq!(huge.size(), u64::MAX);
+
+        let unaccepted = EFIMemoryDesc::new(15, 0, 0, 1, 0);
+        assert_eq!(unaccepted.typ(), EFIMemoryAreaType::EfiUnacceptedMemoryType);
+        let oem = EFIMemoryDesc::new(0x7000_0001, 0, 0, 1, 0);
+        assert_eq!(oem.typ(), EFIMemoryAreaType::EfiUnknown(0x7000_0001));
+        for typ in 0..=16 {
+            assert_eq!(u32::from(EFIMemoryAreaType::from(typ)), typ);
+        }
+        assert_eq!(
+            EFIMemoryAreaType::EfiUnknown(7),
+            EFIMemoryAreaType::EfiConventionalMemory
+        );
+    }
+
+    /// Real firmware uses descriptors of 48 bytes. Only complete descriptors
+    /// must be returned.
+    #[test]
+    fn test_efi_memory_map_desc_size() {
+        let mut entries = std::vec::Vec::new();
+        for i in 0..3 {
+            entries.extend(&desc_bytes(&EFIMemoryDesc::new(7, i * 0x1000, 0, 1, 0xf)));
+            entries.extend(&[0xff; 8]);
+        }
+        // the start of a fourth descriptor
+        entries.extend(&[0; 40]);
+        let tag = get_tag(TagType::EfiMmap, 48, 1, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<EFIMemoryMapTag>().as_ref().unwrap() };
+        assert_eq!(tag.desc_size(), 48);
+        assert_eq!(tag.desc_version(), 1);
+        let mut areas = tag.memory_areas();
+        assert_eq!(areas.len(), 3);
+        for i in 0..3 {
+            assert_eq!(areas.next().unwrap().physical_address(), i * 0x1000);
+            assert_eq!(areas.len(), 2 - i as usize);
+        }
+        assert!(areas.next().is_none());
+
+        // descriptors that are smaller than `EFIMemoryDesc` are rejected
+        let tag = get_tag(TagType::EfiMmap, 32, 1, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<EFIMemoryMapTag>().as_ref().unwrap() };
+        assert_eq!(tag.memory_areas().len(), 0);
+
+        // a tag without descriptors
+        let tag = get_tag(TagType::EfiMmap, 48, 1, &[]);
+        let tag = unsafe { tag.as_ptr().cast::<EFIMemoryMapTag>().as_ref().unwrap() };
+        assert_eq!(tag.memory_areas().len(), 0);
+    }
+
+    #[test]
+    fn test_empty_memory_maps() {
+        let tag = get_tag(TagType::Mmap, 24, 0, &[]);
+        assert_eq!(tag.len(), 2);
+        let tag = unsafe { &*(tag.as_ptr() as *const MemoryMapTag) };
+        assert_eq!(tag.all_memory_areas().count(), 0);
+        assert!(std::format!("{:?}", tag).ends_with("areas: [] }"));
+
+        let tag = get_tag(TagType::EfiMmap, 48, 1, &[]);
+        assert_eq!(tag.len(), 2);
+        let tag = unsafe { &*(tag.as_ptr() as *const EFIMemoryMapTag) };
+        assert_eq!(tag.memory_areas().len(), 0);
+        assert!(std::format!("{:?}", tag).ends_with("descs: [] }"));
+    }
+
+    #[test]
+    fn test_normalize_efi_memory_map() {
+        let descs = [
+            EFIMemoryDesc::new(7, 0x10_0000, 0, 0x100, 0xf),
+            // adjacent with the same type and attributes
+            EFIMemoryDesc::new(7, 0x20_0000, 0, 0x100, 0xf),
+            // adjacent with other attributes
+            EFIMemoryDesc::new(7, 0x30_0000, 0, 0x100, 0x8),
+            // runtime services data within the conventional memory
+            EFIMemoryDesc::new(
+                6,
+                0x18_0000,
+                0xffff_8000_0018_0000,
+                0x10,
+                0x8000_0000_0000_000f,
+            ),
+            // boot services data that is less restrictive than runtime services
+            EFIMemoryDesc::new(4, 0x18_8000, 0, 0x10, 0xf),
+        ];
+        let entries: std::vec::Vec<u8> = descs.iter().flat_map(desc_bytes).collect();
+        let tag = get_tag(TagType::EfiMmap, 40, 1, &entries);
+        let tag = unsafe { tag.as_ptr().cast::<EFIMemoryMapTag>().as_ref().unwrap() };
+
+        let mut buffer = [EFIMemoryDesc::new(0, 0, 0, 0, 0); 8];
+        assert_eq!(
+            tag.normalized_memory_areas(&mut buffer).unwrap(),
+            [
+                EFIMemoryDesc::new(7, 0x10_0000, 0, 0x80, 0xf),
+                EFIMemoryDesc::new(
+                    6,
+                    0x18_0000,
+                    0xffff_8000_0018_0000,
+                    0x10,
+                    0x8000_0000_0000_000f
+                ),
+                EFIMemoryDesc::new(4, 0x19_0000, 0, 0x8, 0xf),
+                EFIMemoryDesc::new(7, 0x19_8000, 0, 0x168, 0xf),
+                EFIMemoryDesc::new(7, 0x30_0000, 0, 0x100, 0x8),
+            ]
+        );
+    }
 }
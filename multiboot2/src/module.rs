@@ -1,4 +1,4 @@
-use crate::tag_type::{Tag, TagIter, TagType};
+use crate::tag_type::{Tag, TagIter, TagTrait, TagType, TagTypeId};
 use core::fmt::{Debug, Formatter};
 use core::str::Utf8Error;
 
@@ -7,7 +7,7 @@ use core::str::Utf8Error;
 #[derive(Clone, Copy)]
 #[repr(C, packed)] // only repr(C) would add unwanted padding near name_byte.
 pub struct ModuleTag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     mod_start: u32,
     mod_end: u32,
@@ -15,6 +15,10 @@ pub struct ModuleTag {
     cmdline_str: u8,
 }
 
+unsafe impl TagTrait for ModuleTag {
+    const ID: TagType = TagType::Module;
+}
+
 impl ModuleTag {
     /// Returns the cmdline of the module.
     /// This is an null-terminated UTF-8 string. If this returns `Err` then perhaps the memory
@@ -102,7 +106,7 @@ mod tests {
         //          4 bytes mod_start + 4 bytes mod_end
         let size = (4 + 4 + 4 + 4 + MSG.as_bytes().len() + 1) as u32;
         [
-            &((TagType::Module as u32).to_ne_bytes()),
+            &(u32::from(TagType::Module).to_ne_bytes()),
             &size.to_ne_bytes(),
             &0_u32.to_ne_bytes(),
             &0_u32.to_ne_bytes(),
@@ -0,0 +1,471 @@
+use crate::tag_type::{Tag, TagIter, TagTrait, TagType, TagTypeId};
+use core::convert::TryInto;
+use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::slice;
+use core::str;
+
+/// The size of the fixed part of a BOOTP/DHCP packet, i.e. everything up to
+/// and including the `file` field.
+const BOOTP_HEADER_SIZE: usize = 236;
+
+/// The magic cookie that precedes the DHCP options.
+const DHCP_MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+
+/// This tag contains network information in the format specified as DHCP. It
+/// may be either a real DHCP reply or just the configuration info in the same
+/// format. This tag appears once per card.
+///
+/// The raw packet is returned by [`NetworkTag::dhcp_packet`] and can be parsed
+/// with [`NetworkTag::dhcp`].
+#[repr(C, packed)]
+pub struct NetworkTag {
+    typ: TagTypeId,
+    size: u32,
+    // followed by the DHCP ACK packet
+}
+
+unsafe impl TagTrait for NetworkTag {
+    const ID: TagType = TagType::Network;
+}
+
+impl NetworkTag {
+    /// Returns the raw DHCP/BOOTP packet.
+    pub fn dhcp_packet(&self) -> &[u8] {
+        let len = self.size as usize - size_of::<Self>();
+        unsafe {
+            let packet = (self as *const Self).add(1) as *const u8;
+            slice::from_raw_parts(packet, len)
+        }
+    }
+
+    /// Parses the DHCP/BOOTP packet. Returns `None`, if the packet is too
+    /// short for the BOOTP header.
+    pub fn dhcp(&self) -> Option<DhcpPacket<'_>> {
+        DhcpPacket::new(self.dhcp_packet())
+    }
+}
+
+impl Debug for NetworkTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NetworkTag")
+            .field("typ", &{ self.typ })
+            .field("size", &{ self.size })
+            .field("dhcp", &self.dhcp())
+            .finish()
+    }
+}
+
+pub fn network_iter(iter: TagIter) -> NetworkIter {
+    NetworkIter { iter }
+}
+
+/// An iterator over all network tags.
+#[derive(Clone)]
+pub struct NetworkIter<'a> {
+    iter: TagIter<'a>,
+}
+
+impl<'a> Iterator for NetworkIter<'a> {
+    type Item = &'a NetworkTag;
+
+    fn next(&mut self) -> Option<&'a NetworkTag> {
+        self.iter
+            .find(|x| x.typ == TagType::Network)
+            .map(|tag| unsafe { &*(tag as *const Tag as *const NetworkTag) })
+    }
+}
+
+impl<'a> Debug for NetworkIter<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|tag| {
+            list.entry(&tag);
+        });
+        list.finish()
+    }
+}
+
+/// A view on a BOOTP/DHCP packet as defined in RFC 951 and RFC 2131. All
+/// multi-byte fields are in network byte order (big endian) and converted by
+/// the getters.
+///
+/// # Examples
+///
+/// ```ignore
+/// use multiboot2::DhcpOption;
+///
+/// for tag in boot_info.network_tags() {
+///     let dhcp = tag.dhcp().unwrap();
+///     let ip = dhcp.yiaddr();
+///     let next_server = dhcp.siaddr();
+///     let gateway = dhcp.options().find_map(|option| match option {
+///         DhcpOption::Router(routers) => routers.iter().next(),
+///         _ => None,
+///     });
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DhcpPacket<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> DhcpPacket<'a> {
+    /// Creates a view on the given packet. Returns `None`, if the packet is
+    /// too short for the BOOTP header.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < BOOTP_HEADER_SIZE {
+            None
+        } else {
+            Some(Self { bytes })
+        }
+    }
+
+    /// Returns the message op code: 1 for BOOTREQUEST, 2 for BOOTREPLY.
+    pub fn op(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    /// Returns the hardware address type, e.g. 1 for Ethernet.
+    pub fn htype(&self) -> u8 {
+        self.bytes[1]
+    }
+
+    /// Returns the hardware address length, e.g. 6 for Ethernet.
+    pub fn hlen(&self) -> u8 {
+        self.bytes[2]
+    }
+
+    /// Returns the number of relay agent hops.
+    pub fn hops(&self) -> u8 {
+        self.bytes[3]
+    }
+
+    /// Returns the transaction ID.
+    pub fn xid(&self) -> u32 {
+        u32::from_be_bytes(self.ipv4(4))
+    }
+
+    /// Returns the seconds elapsed since the client began the address
+    /// acquisition.
+    pub fn secs(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[8], self.bytes[9]])
+    }
+
+    /// Returns the flags, e.g. `0x8000` for the broadcast flag.
+    pub fn flags(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[10], self.bytes[11]])
+    }
+
+    /// Returns the client IP address.
+    pub fn ciaddr(&self) -> [u8; 4] {
+        self.ipv4(12)
+    }
+
+    /// Returns the IP address that the server assigned to the client ("your"
+    /// IP address).
+    pub fn yiaddr(&self) -> [u8; 4] {
+        self.ipv4(16)
+    }
+
+    /// Returns the IP address of the next server to use in bootstrap, e.g. the
+    /// TFTP server.
+    pub fn siaddr(&self) -> [u8; 4] {
+        self.ipv4(20)
+    }
+
+    /// Returns the relay agent IP address.
+    pub fn giaddr(&self) -> [u8; 4] {
+        self.ipv4(24)
+    }
+
+    /// Returns the client hardware address. The length is bounded by the
+    /// 16 bytes that are reserved for it in the packet.
+    pub fn chaddr(&self) -> &'a [u8] {
+        let len = (self.hlen() as usize).min(16);
+        &self.bytes[28..28 + len]
+    }
+
+    /// Returns the optional server host name without the terminating null
+    /// byte.
+    pub fn sname(&self) -> &'a [u8] {
+        Self::null_terminated(&self.bytes[44..108])
+    }
+
+    /// Returns the boot file name without the terminating null byte.
+    pub fn file(&self) -> &'a [u8] {
+        Self::null_terminated(&self.bytes[108..BOOTP_HEADER_SIZE])
+    }
+
+    /// Returns an iterator over the DHCP options. The iterator is empty if the
+    /// packet has no options, i.e. if the magic cookie is missing.
+    pub fn options(&self) -> DhcpOptionIter<'a> {
+        let options = &self.bytes[BOOTP_HEADER_SIZE..];
+        if options.len() >= DHCP_MAGIC_COOKIE.len()
+            && options[..DHCP_MAGIC_COOKIE.len()] == DHCP_MAGIC_COOKIE
+        {
+            DhcpOptionIter {
+                remaining: &options[DHCP_MAGIC_COOKIE.len()..],
+            }
+        } else {
+            DhcpOptionIter { remaining: &[] }
+        }
+    }
+
+    fn ipv4(&self, offset: usize) -> [u8; 4] {
+        [
+            self.bytes[offset],
+            self.bytes[offset + 1],
+            self.bytes[offset + 2],
+            self.bytes[offset + 3],
+        ]
+    }
+
+    fn null_terminated(bytes: &[u8]) -> &[u8] {
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        &bytes[..len]
+    }
+}
+
+impl Debug for DhcpPacket<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DhcpPacket")
+            .field("op", &self.op())
+            .field("xid", &self.xid())
+            .field("ciaddr", &self.ciaddr())
+            .field("yiaddr", &self.yiaddr())
+            .field("siaddr", &self.siaddr())
+            .field("giaddr", &self.giaddr())
+            .field("chaddr", &self.chaddr())
+            .field("options", &self.options())
+            .finish()
+    }
+}
+
+/// A list of IPv4 addresses of a DHCP option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ipv4AddrList<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Ipv4AddrList<'a> {
+    /// Returns an iterator over the addresses of the list.
+    pub fn iter(&self) -> impl Iterator<Item = [u8; 4]> + 'a {
+        self.bytes
+            .chunks_exact(4)
+            .map(|addr| [addr[0], addr[1], addr[2], addr[3]])
+    }
+}
+
+/// A decoded DHCP option. Options that are not known by this crate or that
+/// have an unexpected length are returned as [`DhcpOption::Unknown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DhcpOption<'a> {
+    /// The subnet mask of the client (option 1).
+    SubnetMask([u8; 4]),
+    /// The routers on the subnet of the client in order of preference (option 3).
+    Router(Ipv4AddrList<'a>),
+    /// The DNS servers in order of preference (option 6).
+    DomainNameServer(Ipv4AddrList<'a>),
+    /// The host name of the client (option 12).
+    HostName(&'a str),
+    /// The IP address lease time in seconds (option 51).
+    LeaseTime(u32),
+    /// Any other option with its raw data.
+    Unknown {
+        /// The option code.
+        code: u8,
+        /// The data of the option, without the code and the length.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> DhcpOption<'a> {
+    fn new(code: u8, data: &'a [u8]) -> Self {
+        let option = match code {
+            1 => data.try_into().ok().map(DhcpOption::SubnetMask),
+            3 if Self::is_addr_list(data) => Some(DhcpOption::Router(Ipv4AddrList { bytes: data })),
+            6 if Self::is_addr_list(data) => {
+                Some(DhcpOption::DomainNameServer(Ipv4AddrList { bytes: data }))
+            }
+            12 => str::from_utf8(data).ok().map(DhcpOption::HostName),
+            51 => data
+                .try_into()
+                .ok()
+                .map(|bytes| DhcpOption::LeaseTime(u32::from_be_bytes(bytes))),
+            _ => None,
+        };
+        option.unwrap_or(DhcpOption::Unknown { code, data })
+    }
+
+    fn is_addr_list(data: &[u8]) -> bool {
+        !data.is_empty() && data.chunks_exact(4).remainder().is_empty()
+    }
+}
+
+/// An iterator over the DHCP options of a [`DhcpPacket`]. Pad options are
+/// skipped. The iteration stops at the end option or at the first option
+/// that is not completely within the packet.
+#[derive(Clone)]
+pub struct DhcpOptionIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Iterator for DhcpOptionIter<'a> {
+    type Item = DhcpOption<'a>;
+
+    fn next(&mut self) -> Option<DhcpOption<'a>> {
+        /// Single byte option to align the following options.
+        const PAD: u8 = 0;
+        /// Single byte option that marks the end of the options.
+        const END: u8 = 255;
+
+        loop {
+            let bytes = self.remaining;
+            // stop the iteration in any case, if the option is malformed
+            self.remaining = &[];
+
+            let (&code, rest) = bytes.split_first()?;
+            match code {
+                PAD => self.remaining = rest,
+                END => return None,
+                code => {
+                    let (&len, rest) = rest.split_first()?;
+                    if rest.len() < len as usize {
+                        return None;
+                    }
+                    let (data, rest) = rest.split_at(len as usize);
+                    self.remaining = rest;
+                    return Some(DhcpOption::new(code, data));
+                }
+            }
+        }
+    }
+}
+
+impl Debug for DhcpOptionIter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|option| {
+            list.entry(&option);
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DhcpOption, DhcpPacket, NetworkTag};
+    use crate::TagType;
+
+    /// Returns a DHCP ACK packet similar to the one of a PXE boot in QEMU.
+    fn get_packet() -> std::vec::Vec<u8> {
+        let mut packet = std::vec![0_u8; 236];
+        packet[0] = 2; // op: BOOTREPLY
+        packet[1] = 1; // htype: Ethernet
+        packet[2] = 6; // hlen
+        packet[4..8].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]); // xid
+        packet[16..20].copy_from_slice(&[10, 0, 2, 15]); // yiaddr
+        packet[20..24].copy_from_slice(&[10, 0, 2, 2]); // siaddr
+        packet[28..34].copy_from_slice(&[0x52, 0x54, 0, 0x12, 0x34, 0x56]); // chaddr
+        packet[108..119].copy_from_slice(b"pxelinux.0\0"); // file
+        packet.extend(&[99, 130, 83, 99]); // magic cookie
+        packet.extend(&[53, 1, 5]); // message type: DHCPACK
+        packet.extend(&[1, 4, 255, 255, 255, 0]); // subnet mask
+        packet.extend(&[0, 0]); // pad
+        packet.extend(&[3, 4, 10, 0, 2, 2]); // router
+        packet.extend(&[6, 8, 10, 0, 2, 3, 1, 1, 1, 1]); // DNS servers
+        packet.extend(&[51, 4, 0, 1, 0x51, 0x80]); // lease time: 86400 s
+        packet.extend(&[12, 4, b'h', b'o', b's', b't']); // host name
+        packet.extend(&[255]); // end
+        packet.extend(&[1, 4, 0, 0, 0, 0]); // garbage after the end
+        packet
+    }
+
+    /// Returns the tag structure in bytes in native endian format.
+    fn get_bytes() -> std::vec::Vec<u8> {
+        let packet = get_packet();
+        let size = (8 + packet.len()) as u32;
+        let mut bytes = std::vec::Vec::new();
+        bytes.extend(&u32::from(TagType::Network).to_ne_bytes());
+        bytes.extend(&size.to_ne_bytes());
+        bytes.extend(&packet);
+        bytes
+    }
+
+    #[test]
+    fn test_parse() {
+        let tag = get_bytes();
+        let tag = unsafe { tag.as_ptr().cast::<NetworkTag>().as_ref().unwrap() };
+        assert_eq!(tag.dhcp_packet(), &get_packet()[..]);
+
+        let dhcp = tag.dhcp().unwrap();
+        assert_eq!(dhcp.op(), 2);
+        assert_eq!(dhcp.htype(), 1);
+        assert_eq!(dhcp.xid(), 0xdeadbeef);
+        assert_eq!(dhcp.ciaddr(), [0, 0, 0, 0]);
+        assert_eq!(dhcp.yiaddr(), [10, 0, 2, 15]);
+        assert_eq!(dhcp.siaddr(), [10, 0, 2, 2]);
+        assert_eq!(dhcp.chaddr(), &[0x52, 0x54, 0, 0x12, 0x34, 0x56]);
+        assert_eq!(dhcp.sname(), b"");
+        assert_eq!(dhcp.file(), b"pxelinux.0");
+
+        let mut options = dhcp.options();
+        assert_eq!(
+            options.next(),
+            Some(DhcpOption::Unknown {
+                code: 53,
+                data: &[5]
+            })
+        );
+        assert_eq!(
+            options.next(),
+            Some(DhcpOption::SubnetMask([255, 255, 255, 0]))
+        );
+        match options.next() {
+            Some(DhcpOption::Router(routers)) => {
+                assert_eq!(
+                    routers.iter().collect::<std::vec::Vec<_>>(),
+                    [[10, 0, 2, 2]]
+                )
+            }
+            option => panic!("unexpected option {:?}", option),
+        }
+        match options.next() {
+            Some(DhcpOption::DomainNameServer(servers)) => assert_eq!(
+                servers.iter().collect::<std::vec::Vec<_>>(),
+                [[10, 0, 2, 3], [1, 1, 1, 1]]
+            ),
+            option => panic!("unexpected option {:?}", option),
+        }
+        assert_eq!(options.next(), Some(DhcpOption::LeaseTime(86400)));
+        assert_eq!(options.next(), Some(DhcpOption::HostName("host")));
+        assert_eq!(options.next(), None);
+    }
+
+    /// Options with an unexpected length are returned as raw data and
+    /// truncated options end the iteration.
+    #[test]
+    fn test_malformed_options() {
+        let mut packet = get_packet();
+        packet.truncate(240);
+        packet.extend(&[1, 3, 255, 255, 255]); // subnet mask with a wrong length
+        packet.extend(&[3, 8, 10, 0, 2, 2]); // truncated router option
+        let dhcp = DhcpPacket::new(&packet).unwrap();
+        let mut options = dhcp.options();
+        assert_eq!(
+            options.next(),
+            Some(DhcpOption::Unknown {
+                code: 1,
+                data: &[255, 255, 255]
+            })
+        );
+        assert_eq!(options.next(), None);
+
+        // BOOTP packets without the magic cookie have no options
+        packet.truncate(236);
+        assert_eq!(DhcpPacket::new(&packet).unwrap().options().count(), 0);
+        packet.truncate(235);
+        assert!(DhcpPacket::new(&packet).is_none());
+    }
+}
@@ -7,19 +7,40 @@
 //! `RsdpV2Tag` will be provided, which can be accessed with `rsdp_v2_tag`.
 //!
 //! Even though the bootloader should give the address of the real RSDP/XSDT, the checksum and
-//! signature should be manually verified.
-use crate::TagType;
+//! signature should be manually verified. [`Rsdp`] is a view over both tags that does this via
+//! [`Rsdp::validate`].
+use crate::{TagTrait, TagType, TagTypeId};
+use core::mem::size_of;
 use core::slice;
 use core::str;
 use core::str::Utf8Error;
 
 const RSDPV1_LENGTH: usize = 20;
+const RSDPV2_LENGTH: usize = 36;
+const RSDP_SIGNATURE: [u8; 8] = *b"RSD PTR ";
+
+/// Returns the bytes of the RSDP within a tag, i.e. everything after the tag header up to the
+/// `size` of the tag. The bytes are clamped to the size of `T`, as a tag that was copied out of
+/// the boot information has no bytes behind it.
+fn rsdp_bytes<T>(tag: &T, size: u32) -> &[u8] {
+    let size = (size as usize).max(8).min(size_of::<T>());
+    // SAFETY: `size` doesn't exceed the size of `T`.
+    unsafe { &slice::from_raw_parts(tag as *const T as *const u8, size)[8..] }
+}
+
+/// Checks that the first `length` bytes of the RSDP sum up to zero. Returns `false`, if the RSDP
+/// is shorter than `length`.
+fn checksum_is_valid(rsdp: &[u8], length: usize) -> bool {
+    rsdp.get(..length)
+        .map(|bytes| bytes.iter().fold(0u8, |acc, val| acc.wrapping_add(*val)) == 0)
+        .unwrap_or(false)
+}
 
 /// This tag contains a copy of RSDP as defined per ACPI 1.0 specification.
 #[derive(Clone, Copy, Debug)]
 #[repr(C, packed)]
 pub struct RsdpV1Tag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     signature: [u8; 8],
     checksum: u8,
@@ -28,7 +49,30 @@ pub struct RsdpV1Tag {
     rsdt_address: u32, // This is the PHYSICAL address of the RSDT
 }
 
+unsafe impl TagTrait for RsdpV1Tag {
+    const ID: TagType = TagType::AcpiV1;
+}
+
 impl RsdpV1Tag {
+    /// Creates a new tag from the fields of a RSDP as defined per ACPI 1.0 specification.
+    pub fn new(
+        signature: [u8; 8],
+        checksum: u8,
+        oem_id: [u8; 6],
+        revision: u8,
+        rsdt_address: u32,
+    ) -> Self {
+        Self {
+            typ: TagType::AcpiV1.into(),
+            size: size_of::<Self>() as u32,
+            signature,
+            checksum,
+            oem_id,
+            revision,
+            rsdt_address,
+        }
+    }
+
     /// The "RSD PTR " marker singature.
     ///
     /// This is originally a 8-byte C string (not null terminated!) that must contain "RSD PTR "
@@ -36,14 +80,10 @@ impl RsdpV1Tag {
         str::from_utf8(&self.signature)
     }
 
-    /// Validation of the RSDPv1 checksum
+    /// Validation of the RSDPv1 checksum. Returns `false`, if the tag is too small to contain the
+    /// RSDP.
     pub fn checksum_is_valid(&self) -> bool {
-        let bytes =
-            unsafe { slice::from_raw_parts(self as *const _ as *const u8, RSDPV1_LENGTH + 8) };
-        bytes[8..]
-            .iter()
-            .fold(0u8, |acc, val| acc.wrapping_add(*val))
-            == 0
+        checksum_is_valid(self.bytes(), RSDPV1_LENGTH)
     }
 
     /// An OEM-supplied string that identifies the OEM.
@@ -57,8 +97,13 @@ impl RsdpV1Tag {
     }
 
     /// The physical (I repeat: physical) address of the RSDT table.
-    pub fn rsdt_address(&self) -> usize {
-        self.rsdt_address as usize
+    pub fn rsdt_address(&self) -> u32 {
+        self.rsdt_address
+    }
+
+    /// The bytes of the RSDP, bounded by the size of the tag.
+    fn bytes(&self) -> &[u8] {
+        rsdp_bytes(self, self.size)
     }
 }
 
@@ -66,20 +111,52 @@ impl RsdpV1Tag {
 #[derive(Clone, Copy, Debug)]
 #[repr(C, packed)]
 pub struct RsdpV2Tag {
-    typ: TagType,
+    typ: TagTypeId,
     size: u32,
     signature: [u8; 8],
     checksum: u8,
     oem_id: [u8; 6],
     revision: u8,
-    _rsdt_address: u32,
+    rsdt_address: u32, // This is the PHYSICAL address of the RSDT
     length: u32,
     xsdt_address: u64, // This is the PHYSICAL address of the XSDT
     ext_checksum: u8,
     _reserved: [u8; 3],
 }
 
+unsafe impl TagTrait for RsdpV2Tag {
+    const ID: TagType = TagType::AcpiV2;
+}
+
 impl RsdpV2Tag {
+    /// Creates a new tag from the fields of a RSDP as defined per ACPI 2.0 or later
+    /// specification.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        signature: [u8; 8],
+        checksum: u8,
+        oem_id: [u8; 6],
+        revision: u8,
+        rsdt_address: u32,
+        length: u32,
+        xsdt_address: u64,
+        ext_checksum: u8,
+    ) -> Self {
+        Self {
+            typ: TagType::AcpiV2.into(),
+            size: size_of::<Self>() as u32,
+            signature,
+            checksum,
+            oem_id,
+            revision,
+            rsdt_address,
+            length,
+            xsdt_address,
+            ext_checksum,
+            _reserved: [0; 3],
+        }
+    }
+
     /// The "RSD PTR " marker signature.
     ///
     /// This is originally a 8-byte C string (not null terminated!) that must contain "RSD PTR ".
@@ -87,15 +164,15 @@ impl RsdpV2Tag {
         str::from_utf8(&self.signature)
     }
 
-    /// Validation of the RSDPv2 extended checksum
+    /// Validation of the RSDPv2 extended checksum over [`Self::length`] bytes. Returns `false`,
+    /// if the length exceeds the size of the tag.
     pub fn checksum_is_valid(&self) -> bool {
-        let bytes = unsafe {
-            slice::from_raw_parts(self as *const _ as *const u8, self.length as usize + 8)
-        };
-        bytes[8..]
-            .iter()
-            .fold(0u8, |acc, val| acc.wrapping_add(*val))
-            == 0
+        checksum_is_valid(self.bytes(), self.length as usize)
+    }
+
+    /// Validation of the checksum over the first 20 bytes, i.e. the fields of the RSDPv1.
+    pub fn v1_checksum_is_valid(&self) -> bool {
+        checksum_is_valid(self.bytes(), RSDPV1_LENGTH)
     }
 
     /// An OEM-supplied string that identifies the OEM.
@@ -108,6 +185,16 @@ impl RsdpV2Tag {
         self.revision
     }
 
+    /// The physical (I repeat: physical) address of the RSDT table.
+    pub fn rsdt_address(&self) -> u32 {
+        self.rsdt_address
+    }
+
+    /// The length of the RSDP in bytes, as reported by the firmware.
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
     /// Physical address of the XSDT table.
     ///
     /// On x86, this is truncated from 64-bit to 32-bit.
@@ -119,4 +206,233 @@ impl RsdpV2Tag {
     pub fn ext_checksum(&self) -> u8 {
         self.ext_checksum
     }
+
+    /// The bytes of the RSDP, bounded by the size of the tag.
+    fn bytes(&self) -> &[u8] {
+        rsdp_bytes(self, self.size)
+    }
+}
+
+/// Errors that [`Rsdp::validate`] reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RsdpError {
+    /// The signature is not `"RSD PTR "`. Holds the signature.
+    InvalidSignature([u8; 8]),
+    /// The RSDP is larger than the tag that contains it or, for the RSDPv2, smaller than its
+    /// fields. Holds the length of the RSDP.
+    InvalidLength(u32),
+    /// The checksum over the fields of the RSDPv1 is not valid.
+    InvalidChecksum,
+    /// The extended checksum over the whole RSDPv2 is not valid.
+    InvalidExtendedChecksum,
+}
+
+/// A view over the RSDP of either the [`RsdpV1Tag`] or the [`RsdpV2Tag`].
+///
+/// # Examples
+///
+/// ```ignore
+/// if let Some(rsdp) = boot_info.rsdp() {
+///     rsdp.validate().expect("the RSDP must be valid");
+///     let root = rsdp.xsdt_address().unwrap_or(u64::from(rsdp.rsdt_address()));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum Rsdp<'a> {
+    /// The RSDP as defined per ACPI 1.0 specification.
+    V1(&'a RsdpV1Tag),
+    /// The RSDP as defined per ACPI 2.0 or later specification.
+    V2(&'a RsdpV2Tag),
+}
+
+impl<'a> Rsdp<'a> {
+    /// Validates the signature, the length and the checksums. The checksums are bounded by the
+    /// size of the tag.
+    pub fn validate(&self) -> Result<(), RsdpError> {
+        if self.signature_bytes() != RSDP_SIGNATURE {
+            return Err(RsdpError::InvalidSignature(self.signature_bytes()));
+        }
+        let (bytes, length) = match self {
+            Rsdp::V1(tag) => (tag.bytes(), RSDPV1_LENGTH),
+            Rsdp::V2(tag) => (tag.bytes(), tag.length as usize),
+        };
+        if length < self.min_length() || length > bytes.len() {
+            return Err(RsdpError::InvalidLength(length as u32));
+        }
+        if !checksum_is_valid(bytes, RSDPV1_LENGTH) {
+            return Err(RsdpError::InvalidChecksum);
+        }
+        if let Rsdp::V2(_) = self {
+            if !checksum_is_valid(bytes, length) {
+                return Err(RsdpError::InvalidExtendedChecksum);
+            }
+        }
+        Ok(())
+    }
+
+    /// The "RSD PTR " marker signature. See [`Self::validate`].
+    pub fn signature(&self) -> Result<&'a str, Utf8Error> {
+        match *self {
+            Rsdp::V1(tag) => tag.signature(),
+            Rsdp::V2(tag) => tag.signature(),
+        }
+    }
+
+    /// An OEM-supplied string that identifies the OEM.
+    pub fn oem_id(&self) -> Result<&'a str, Utf8Error> {
+        match *self {
+            Rsdp::V1(tag) => tag.oem_id(),
+            Rsdp::V2(tag) => tag.oem_id(),
+        }
+    }
+
+    /// The revision of the ACPI.
+    pub fn revision(&self) -> u8 {
+        match self {
+            Rsdp::V1(tag) => tag.revision(),
+            Rsdp::V2(tag) => tag.revision(),
+        }
+    }
+
+    /// The physical address of the RSDT table.
+    pub fn rsdt_address(&self) -> u32 {
+        match self {
+            Rsdp::V1(tag) => tag.rsdt_address,
+            Rsdp::V2(tag) => tag.rsdt_address,
+        }
+    }
+
+    /// The physical address of the XSDT table. Returns `None` for the RSDPv1 and if the address
+    /// of the RSDPv2 is zero.
+    pub fn xsdt_address(&self) -> Option<u64> {
+        match self {
+            Rsdp::V1(_) => None,
+            Rsdp::V2(tag) => Some(tag.xsdt_address).filter(|&address| address != 0),
+        }
+    }
+
+    /// The length of the RSDP in bytes: 20 for the RSDPv1 and the length that the firmware
+    /// reports for the RSDPv2.
+    pub fn length(&self) -> u32 {
+        match self {
+            Rsdp::V1(_) => RSDPV1_LENGTH as u32,
+            Rsdp::V2(tag) => tag.length,
+        }
+    }
+
+    /// The raw signature.
+    fn signature_bytes(&self) -> [u8; 8] {
+        match self {
+            Rsdp::V1(tag) => tag.signature,
+            Rsdp::V2(tag) => tag.signature,
+        }
+    }
+
+    /// The smallest valid length of the RSDP.
+    fn min_length(&self) -> usize {
+        match self {
+            Rsdp::V1(_) => RSDPV1_LENGTH,
+            Rsdp::V2(_) => RSDPV2_LENGTH,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rsdp, RsdpError, RsdpV1Tag, RsdpV2Tag};
+
+    /// Returns the RSDPv2 tag with valid checksums.
+    fn rsdp_v2(length: u32) -> RsdpV2Tag {
+        let sum = |tag: &RsdpV2Tag, length: usize| {
+            tag.bytes()[..length]
+                .iter()
+                .fold(0u8, |acc, val| acc.wrapping_sub(*val))
+        };
+        let new = |checksum, ext_checksum| {
+            RsdpV2Tag::new(
+                *b"RSD PTR ",
+                checksum,
+                *b"BOCHS ",
+                2,
+                0x7fe18dc,
+                length,
+                0x7fe1900,
+                ext_checksum,
+            )
+        };
+        let checksum = sum(&new(0, 0), 20);
+        let ext_checksum = sum(&new(checksum, 0), 36);
+        new(checksum, ext_checksum)
+    }
+
+    #[test]
+    fn test_rsdp_v1() {
+        let tag = RsdpV1Tag::new(*b"RSD PTR ", 0, *b"BOCHS ", 0, 0x7fe18dc);
+        let rsdp = Rsdp::V1(&tag);
+        assert_eq!(rsdp.validate(), Err(RsdpError::InvalidChecksum));
+        let checksum = tag
+            .bytes()
+            .iter()
+            .fold(0u8, |acc, val| acc.wrapping_sub(*val));
+        let tag = RsdpV1Tag::new(*b"RSD PTR ", checksum, *b"BOCHS ", 0, 0x7fe18dc);
+        let rsdp = Rsdp::V1(&tag);
+        assert!(tag.checksum_is_valid());
+        assert_eq!(rsdp.validate(), Ok(()));
+        assert_eq!(rsdp.oem_id(), Ok("BOCHS "));
+        assert_eq!(rsdp.revision(), 0);
+        assert_eq!(rsdp.rsdt_address(), 0x7fe18dc);
+        assert_eq!(rsdp.xsdt_address(), None);
+        assert_eq!(rsdp.length(), 20);
+
+        let tag = RsdpV1Tag::new(*b"RSD PTR!", checksum, *b"BOCHS ", 0, 0x7fe18dc);
+        assert_eq!(
+            Rsdp::V1(&tag).validate(),
+            Err(RsdpError::InvalidSignature(*b"RSD PTR!"))
+        );
+    }
+
+    #[test]
+    fn test_rsdp_v2() {
+        let tag = rsdp_v2(36);
+        let rsdp = Rsdp::V2(&tag);
+        assert!(tag.checksum_is_valid());
+        assert!(tag.v1_checksum_is_valid());
+        assert_eq!(rsdp.validate(), Ok(()));
+        assert_eq!(rsdp.signature(), Ok("RSD PTR "));
+        assert_eq!(rsdp.revision(), 2);
+        assert_eq!(rsdp.rsdt_address(), 0x7fe18dc);
+        assert_eq!(rsdp.xsdt_address(), Some(0x7fe1900));
+        assert_eq!(rsdp.length(), 36);
+        assert_eq!(tag.length(), 36);
+
+        // the length must not exceed the size of the tag
+        let tag = rsdp_v2(0xffff_ffff);
+        assert!(!tag.checksum_is_valid());
+        assert_eq!(
+            Rsdp::V2(&tag).validate(),
+            Err(RsdpError::InvalidLength(0xffff_ffff))
+        );
+        let tag = rsdp_v2(20);
+        assert_eq!(Rsdp::V2(&tag).validate(), Err(RsdpError::InvalidLength(20)));
+
+        let mut tag = rsdp_v2(36);
+        tag.ext_checksum = tag.ext_checksum.wrapping_add(1);
+        assert!(tag.v1_checksum_is_valid());
+        assert_eq!(
+            Rsdp::V2(&tag).validate(),
+            Err(RsdpError::InvalidExtendedChecksum)
+        );
+
+        // a copied tag has no bytes behind it, even if its size claims so
+        let mut tag = rsdp_v2(0x100);
+        tag.size = 0x1000;
+        assert!(!tag.checksum_is_valid());
+        assert_eq!(
+            Rsdp::V2(&tag).validate(),
+            Err(RsdpError::InvalidLength(0x100))
+        );
+        let mut tag = RsdpV1Tag::new(*b"RSD PTR ", 0, *b"BOCHS ", 0, 0);
+        tag.size = 0x1000;
+        assert_eq!(tag.bytes().len(), 20);
+    }
 }
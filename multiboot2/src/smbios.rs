@@ -0,0 +1,277 @@
+use crate::{TagTrait, TagType, TagTypeId};
+use core::fmt::{Debug, Formatter};
+use core::mem::size_of;
+use core::slice;
+use core::str::Utf8Error;
+
+/// The size of the header of each SMBIOS structure: type, length and handle.
+const STRUCTURE_HEADER_SIZE: usize = 4;
+
+/// The SMBIOS structure type that marks the end of the structure table.
+const END_OF_TABLE: u8 = 127;
+
+/// This tag contains a copy of the SMBIOS tables as well as their version.
+///
+/// The structures of the table can be iterated with [`SmbiosTag::structures`].
+#[repr(C, packed)]
+pub struct SmbiosTag {
+    typ: TagTypeId,
+    size: u32,
+    major: u8,
+    minor: u8,
+    _reserved: [u8; 6],
+    // followed by the tables
+}
+
+unsafe impl TagTrait for SmbiosTag {
+    const ID: TagType = TagType::Smbios;
+}
+
+impl SmbiosTag {
+    /// Returns the major version of SMBIOS.
+    pub fn major(&self) -> u8 {
+        self.major
+    }
+
+    /// Returns the minor version of SMBIOS.
+    pub fn minor(&self) -> u8 {
+        self.minor
+    }
+
+    /// Returns the raw bytes of the SMBIOS tables.
+    pub fn tables(&self) -> &[u8] {
+        let len = self.size as usize - size_of::<Self>();
+        unsafe {
+            let tables = (self as *const Self).add(1) as *const u8;
+            slice::from_raw_parts(tables, len)
+        }
+    }
+
+    /// Returns an iterator over all SMBIOS structures of the tables.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let smbios = boot_info.smbios_tag().unwrap();
+    /// // System Information
+    /// let system = smbios.structures().find(|s| s.typ() == 1).unwrap();
+    /// let manufacturer = system.string(system.formatted()[4]);
+    /// ```
+    pub fn structures(&self) -> SmbiosStructureIter<'_> {
+        SmbiosStructureIter::new(self.tables())
+    }
+}
+
+impl Debug for SmbiosTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SmbiosTag")
+            .field("typ", &{ self.typ })
+            .field("size", &{ self.size })
+            .field("major", &self.major)
+            .field("minor", &self.minor)
+            .field("structures", &self.structures())
+            .finish()
+    }
+}
+
+/// A single SMBIOS structure, consisting of the formatted area and the
+/// string set that follows it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SmbiosStructure<'a> {
+    formatted: &'a [u8],
+    strings: &'a [u8],
+}
+
+impl<'a> SmbiosStructure<'a> {
+    /// Returns the type of the structure, e.g. 1 for the System Information
+    /// or 17 for a Memory Device.
+    pub fn typ(&self) -> u8 {
+        self.formatted[0]
+    }
+
+    /// Returns the handle of the structure. Other structures refer to this
+    /// structure by its handle.
+    pub fn handle(&self) -> u16 {
+        u16::from_le_bytes([self.formatted[2], self.formatted[3]])
+    }
+
+    /// Returns the formatted area of the structure, including the header with
+    /// the type, the length and the handle. The offsets of the fields that
+    /// are given by the SMBIOS specification can be used as indices.
+    pub fn formatted(&self) -> &'a [u8] {
+        self.formatted
+    }
+
+    /// Returns the string with the given (one-based) index of the string set.
+    /// Returns `None` for index 0, which means "no string", and for indices
+    /// that are out of range.
+    pub fn string(&self, index: u8) -> Option<Result<&'a str, Utf8Error>> {
+        let index = (index as usize).checked_sub(1)?;
+        self.strings().nth(index)
+    }
+
+    /// Returns an iterator over all strings of the string set.
+    pub fn strings(&self) -> impl Iterator<Item = Result<&'a str, Utf8Error>> {
+        let strings = self.strings;
+        strings
+            .split(|&byte| byte == 0)
+            .filter(move |_| !strings.is_empty())
+            .map(core::str::from_utf8)
+    }
+}
+
+impl Debug for SmbiosStructure<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SmbiosStructure")
+            .field("typ", &self.typ())
+            .field("handle", &self.handle())
+            .field("formatted (length)", &self.formatted.len())
+            .field("strings (count)", &self.strings().count())
+            .finish()
+    }
+}
+
+/// An iterator over the SMBIOS structures of an [`SmbiosTag`].
+///
+/// The iteration stops after the End-of-Table structure (type 127) or at the
+/// first structure that is not completely within the tables.
+#[derive(Clone)]
+pub struct SmbiosStructureIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> SmbiosStructureIter<'a> {
+    fn new(tables: &'a [u8]) -> Self {
+        Self { remaining: tables }
+    }
+}
+
+impl<'a> Iterator for SmbiosStructureIter<'a> {
+    type Item = SmbiosStructure<'a>;
+
+    fn next(&mut self) -> Option<SmbiosStructure<'a>> {
+        let bytes = self.remaining;
+        // stop the iteration in any case, if the structure is malformed
+        self.remaining = &[];
+
+        if bytes.len() < STRUCTURE_HEADER_SIZE {
+            return None;
+        }
+        let length = bytes[1] as usize;
+        if length < STRUCTURE_HEADER_SIZE || length > bytes.len() {
+            return None;
+        }
+        let (formatted, rest) = bytes.split_at(length);
+
+        // the string set is terminated by two null bytes
+        let strings_len = rest.windows(2).position(|window| window == [0, 0])?;
+        let strings = &rest[..strings_len];
+
+        let structure = SmbiosStructure { formatted, strings };
+        if structure.typ() != END_OF_TABLE {
+            self.remaining = &rest[strings_len + 2..];
+        }
+        Some(structure)
+    }
+}
+
+impl Debug for SmbiosStructureIter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        self.clone().for_each(|structure| {
+            list.entry(&structure);
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmbiosTag;
+    use crate::TagType;
+
+    /// Returns the tag structure in bytes in native endian format.
+    fn get_bytes() -> std::vec::Vec<u8> {
+        let tables: &[u8] = &[
+            // BIOS Information
+            0, 24, 0, 0, // type, length, handle
+            1, 2, 0, 0xe8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // formatted area
+            b'S', b'e', b'a', b'B', b'I', b'O', b'S', 0, // string 1
+            b'r', b'e', b'l', b'-', b'1', 0, // string 2
+            0, // end of the string set
+            // System Information
+            1, 8, 0x00, 0x01, // type, length, handle
+            1, 2, 0, 0, // manufacturer, product name, version, serial number
+            b'Q', b'E', b'M', b'U', 0, // string 1
+            b'S', b't', b'a', b'n', b'd', b'a', b'r', b'd', b' ', b'P', b'C', 0, // string 2
+            0, // end of the string set
+            // Memory Device, without any strings
+            17, 4, 0x00, 0x11, // type, length, handle
+            0, 0, // empty string set
+            // End-of-Table
+            127, 4, 0xff, 0xff, // type, length, handle
+            0, 0, // empty string set
+            // garbage after the end of the table
+            42, 4, 0, 0, 0, 0,
+        ];
+        let size = (16 + tables.len()) as u32;
+        [
+            &(u32::from(TagType::Smbios).to_ne_bytes()) as &[u8],
+            &size.to_ne_bytes(),
+            &[3, 0, 0, 0, 0, 0, 0, 0],
+            tables,
+        ]
+        .iter()
+        .flat_map(|bytes| bytes.iter())
+        .copied()
+        .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let tag = get_bytes();
+        let tag = unsafe { tag.as_ptr().cast::<SmbiosTag>().as_ref().unwrap() };
+        assert_eq!(tag.major(), 3);
+        assert_eq!(tag.minor(), 0);
+        assert_eq!(tag.tables().len(), tag.size as usize - 16);
+
+        let mut structures = tag.structures();
+        let bios = structures.next().unwrap();
+        assert_eq!(bios.typ(), 0);
+        assert_eq!(bios.handle(), 0);
+        assert_eq!(bios.formatted().len(), 24);
+        assert_eq!(bios.string(bios.formatted()[4]), Some(Ok("SeaBIOS")));
+        assert_eq!(bios.string(bios.formatted()[5]), Some(Ok("rel-1")));
+        assert_eq!(bios.string(0), None);
+        assert_eq!(bios.string(3), None);
+
+        let system = structures.next().unwrap();
+        assert_eq!(system.typ(), 1);
+        assert_eq!(system.handle(), 0x100);
+        assert_eq!(system.string(system.formatted()[4]), Some(Ok("QEMU")));
+        assert_eq!(
+            system.string(system.formatted()[5]),
+            Some(Ok("Standard PC"))
+        );
+        assert_eq!(system.string(system.formatted()[6]), None);
+
+        let memory = structures.next().unwrap();
+        assert_eq!(memory.typ(), 17);
+        assert_eq!(memory.handle(), 0x1100);
+        assert_eq!(memory.strings().count(), 0);
+
+        let end = structures.next().unwrap();
+        assert_eq!(end.typ(), 127);
+        assert!(structures.next().is_none());
+    }
+
+    /// A structure that exceeds the tables must end the iteration.
+    #[test]
+    fn test_truncated() {
+        let mut tag = get_bytes();
+        // claim that the BIOS information is bigger than the tables
+        tag[17] = 0xff;
+        let tag = unsafe { tag.as_ptr().cast::<SmbiosTag>().as_ref().unwrap() };
+        assert!(tag.structures().next().is_none());
+    }
+}
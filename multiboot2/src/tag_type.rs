@@ -1,26 +1,52 @@
-//! Module for [`TagType`].
+//! Module for [`TagType`], [`TagTypeId`] and [`Tag`].
 
+use core::cmp::Ordering;
 use core::fmt::{Debug, Formatter};
-use core::hash::Hash;
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
+use core::mem::size_of;
+use core::slice;
+
+/// Serialized form of [`TagType`] that matches the binary representation of the `typ`
+/// field of a [`Tag`] in the Multiboot2 information structure (MBI). Contrary to
+/// [`TagType`], every possible value is valid, which makes it safe to read it
+/// directly from memory that the bootloader prepared.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub struct TagTypeId(u32);
+
+impl TagTypeId {
+    /// Constructor.
+    pub const fn new(val: u32) -> Self {
+        Self(val)
+    }
+
+    /// Returns the raw numeric value of the tag type.
+    pub const fn val(self) -> u32 {
+        self.0
+    }
+}
 
 /// Possible types of a Tag in the Multiboot2 Information Structure (MBI), therefore the value
 /// of the the `typ` property. The names and values are taken from the example C code
 /// at the bottom of the Multiboot2 specification.
-#[repr(u32)]
-#[derive(Copy, Clone, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+///
+/// Each variant corresponds to one numeric [`TagTypeId`]. Types that are not known by this
+/// crate, such as vendor-specific tags or tags of newer versions of the specification,
+/// are represented by [`TagType::Unknown`].
+#[derive(Copy, Clone, Debug)]
 pub enum TagType {
     /// Marks the end of the tags.
-    End = 0,
+    End,
     /// Additional command line string.
     /// For example `''` or `'--my-custom-option foo --provided by_grub`, if your GRUB config
     /// contains `multiboot2 /boot/multiboot2-binary.elf --my-custom-option foo --provided by_grub`
-    Cmdline = 1,
+    Cmdline,
     /// Name of the bootloader, e.g. 'GRUB 2.04-1ubuntu44.2'
-    BootLoaderName = 2,
+    BootLoaderName,
     /// Additional Multiboot modules, which are BLOBs provided in memory. For example an initial
     /// ram disk with essential drivers.
-    Module = 3,
+    Module,
     /// ‘mem_lower’ and ‘mem_upper’ indicate the amount of lower and upper memory, respectively,
     /// in kilobytes. Lower memory starts at address 0, and upper memory starts at address 1
     /// megabyte. The maximum possible value for lower memory is 640 kilobytes. The value returned
@@ -30,12 +56,12 @@ pub enum TagType {
     /// This tag may not be provided by some boot loaders on EFI platforms if EFI boot services are
     /// enabled and available for the loaded image (EFI boot services not terminated tag exists in
     /// Multiboot2 information structure).
-    BasicMeminfo = 4,
+    BasicMeminfo,
     /// This tag indicates which BIOS disk device the boot loader loaded the OS image from. If the
     /// OS image was not loaded from a BIOS disk, then this tag must not be present. The operating
     /// system may use this field as a hint for determining its own root device, but is not
     /// required to.
-    Bootdev = 5,
+    Bootdev,
     /// Memory map. The map provided is guaranteed to list all standard RAM that should be
     /// available for normal use. This type however includes the regions occupied by kernel, mbi,
     /// segments and modules. Kernel must take care not to overwrite these regions.
@@ -43,97 +69,297 @@ pub enum TagType {
     // This tag may not be provided by some boot loaders on EFI platforms if EFI boot services are
     // enabled and available for the loaded image (EFI boot services not terminated tag exists in
     // Multiboot2 information structure).
-    Mmap = 6,
+    Mmap,
     /// Contains the VBE control information returned by the VBE Function 00h and VBE mode
     /// information returned by the VBE Function 01h, respectively. Note that VBE 3.0 defines
     /// another protected mode interface which is incompatible with the old one. If you want to use the new protected mode interface, you will have to find the table yourself.
-    Vbe = 7,
+    Vbe,
     /// Framebuffer.
-    Framebuffer = 8,
+    Framebuffer,
     /// This tag contains section header table from an ELF kernel, the size of each entry, number
     /// of entries, and the string table used as the index of names. They correspond to the
     /// ‘shdr_*’ entries (‘shdr_num’, etc.) in the Executable and Linkable Format (ELF)
     /// specification in the program header.
-    ElfSections = 9,
+    ElfSections,
     /// APM table. See Advanced Power Management (APM) BIOS Interface Specification, for more
     /// information.
-    Apm = 10,
+    Apm,
     /// This tag contains pointer to i386 EFI system table.
-    Efi32 = 11,
+    Efi32,
     /// This tag contains pointer to amd64 EFI system table.
-    Efi64 = 12,
+    Efi64,
     /// This tag contains a copy of SMBIOS tables as well as their version.
-    Smbios = 13,
+    Smbios,
     /// Also called "AcpiOld" in other multiboot2 implementations.
-    AcpiV1 = 14,
+    AcpiV1,
     /// Refers to version 2 and later of Acpi.
     /// Also called "AcpiNew" in other multiboot2 implementations.
-    AcpiV2 = 15,
+    AcpiV2,
     /// This tag contains network information in the format specified as DHCP. It may be either a
     /// real DHCP reply or just the configuration info in the same format. This tag appears once
     /// per card.
-    Network = 16,
+    Network,
     /// This tag contains EFI memory map as per EFI specification.
     /// This tag may not be provided by some boot loaders on EFI platforms if EFI boot services are
     /// enabled and available for the loaded image (EFI boot services not terminated tag exists in Multiboot2 information structure).
-    EfiMmap = 17,
+    EfiMmap,
     /// This tag indicates ExitBootServices wasn't called.
-    EfiBs = 18,
+    EfiBs,
     /// This tag contains pointer to EFI i386 image handle. Usually it is boot loader image handle.
-    Efi32Ih = 19,
+    Efi32Ih,
     /// This tag contains pointer to EFI amd64 image handle. Usually it is boot loader image handle.
-    Efi64Ih = 20,
+    Efi64Ih,
     /// This tag contains image load base physical address. The spec tells
     /// "It is provided only if image has relocatable header tag." but experience showed
     /// that this is not true for at least GRUB 2.
-    LoadBaseAddr = 21,
+    LoadBaseAddr,
+    /// A tag type that is not known by this crate, for example a vendor-specific tag or
+    /// a tag of a newer version of the specification. Holds the raw type ID.
+    Unknown(u32),
+}
+
+impl TagType {
+    /// Returns the raw numeric value of the tag type.
+    pub fn val(self) -> u32 {
+        u32::from(self)
+    }
+}
+
+impl From<u32> for TagType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => TagType::End,
+            1 => TagType::Cmdline,
+            2 => TagType::BootLoaderName,
+            3 => TagType::Module,
+            4 => TagType::BasicMeminfo,
+            5 => TagType::Bootdev,
+            6 => TagType::Mmap,
+            7 => TagType::Vbe,
+            8 => TagType::Framebuffer,
+            9 => TagType::ElfSections,
+            10 => TagType::Apm,
+            11 => TagType::Efi32,
+            12 => TagType::Efi64,
+            13 => TagType::Smbios,
+            14 => TagType::AcpiV1,
+            15 => TagType::AcpiV2,
+            16 => TagType::Network,
+            17 => TagType::EfiMmap,
+            18 => TagType::EfiBs,
+            19 => TagType::Efi32Ih,
+            20 => TagType::Efi64Ih,
+            21 => TagType::LoadBaseAddr,
+            value => TagType::Unknown(value),
+        }
+    }
+}
+
+impl From<TagType> for u32 {
+    fn from(value: TagType) -> Self {
+        match value {
+            TagType::End => 0,
+            TagType::Cmdline => 1,
+            TagType::BootLoaderName => 2,
+            TagType::Module => 3,
+            TagType::BasicMeminfo => 4,
+            TagType::Bootdev => 5,
+            TagType::Mmap => 6,
+            TagType::Vbe => 7,
+            TagType::Framebuffer => 8,
+            TagType::ElfSections => 9,
+            TagType::Apm => 10,
+            TagType::Efi32 => 11,
+            TagType::Efi64 => 12,
+            TagType::Smbios => 13,
+            TagType::AcpiV1 => 14,
+            TagType::AcpiV2 => 15,
+            TagType::Network => 16,
+            TagType::EfiMmap => 17,
+            TagType::EfiBs => 18,
+            TagType::Efi32Ih => 19,
+            TagType::Efi64Ih => 20,
+            TagType::LoadBaseAddr => 21,
+            TagType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<TagTypeId> for TagType {
+    fn from(value: TagTypeId) -> Self {
+        TagType::from(value.0)
+    }
+}
+
+impl From<TagType> for TagTypeId {
+    fn from(value: TagType) -> Self {
+        TagTypeId(u32::from(value))
+    }
+}
+
+// Equality, ordering and hashing are implemented by the numeric value, so that
+// `TagType::Unknown(1)` and `TagType::Cmdline` are considered the same.
+
+impl PartialEq for TagType {
+    fn eq(&self, other: &TagType) -> bool {
+        u32::from(*self) == u32::from(*other)
+    }
+}
+
+impl Eq for TagType {}
+
+impl PartialOrd for TagType {
+    fn partial_cmp(&self, other: &TagType) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TagType {
+    fn cmp(&self, other: &TagType) -> Ordering {
+        u32::from(*self).cmp(&u32::from(*other))
+    }
+}
+
+impl Hash for TagType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u32::from(*self).hash(state)
+    }
 }
 
 // each compare/equal direction must be implemented manually
 impl PartialEq<u32> for TagType {
     fn eq(&self, other: &u32) -> bool {
-        *self as u32 == *other
+        u32::from(*self) == *other
     }
 }
 
 // each compare/equal direction must be implemented manually
 impl PartialEq<TagType> for u32 {
     fn eq(&self, other: &TagType) -> bool {
-        *self == *other as u32
+        *self == u32::from(*other)
+    }
+}
+
+// each compare/equal direction must be implemented manually
+impl PartialEq<TagTypeId> for TagType {
+    fn eq(&self, other: &TagTypeId) -> bool {
+        u32::from(*self) == other.0
+    }
+}
+
+// each compare/equal direction must be implemented manually
+impl PartialEq<TagType> for TagTypeId {
+    fn eq(&self, other: &TagType) -> bool {
+        self.0 == u32::from(*other)
     }
 }
 
 /// All tags that could passed via the Multiboot2 information structure to a payload/program/kernel.
 /// Better not confuse this with the Multiboot2 header tags. They are something different.
-#[derive(Clone, Copy)]
+///
+/// Tags can only be obtained by reference from a [`crate::BootInformation`], for example via
+/// [`crate::BootInformation::tags`]. This also works for tags of a type that is unknown to this
+/// crate.
 #[repr(C)]
 pub struct Tag {
     // u32 value
-    pub typ: TagType,
-    pub size: u32,
+    pub(crate) typ: TagTypeId,
+    pub(crate) size: u32,
     // tag specific fields
 }
 
+impl Tag {
+    /// Returns the type of the tag.
+    pub fn typ(&self) -> TagType {
+        self.typ.into()
+    }
+
+    /// Returns the raw type ID of the tag.
+    pub fn typ_id(&self) -> TagTypeId {
+        self.typ
+    }
+
+    /// Returns the size of the tag in bytes, including the `typ` and `size` fields
+    /// but excluding the padding after the tag.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the tag-specific data of the tag, i.e. all bytes after the `typ` and
+    /// `size` fields without the padding after the tag.
+    pub fn payload(&self) -> &[u8] {
+        let len = (self.size as usize).saturating_sub(size_of::<Tag>());
+        // SAFETY: Tags are only handed out by `TagIter`, which guarantees that
+        // the whole tag is within the bounds of the MBI.
+        unsafe { slice::from_raw_parts((self as *const Tag).add(1) as *const u8, len) }
+    }
+}
+
 impl Debug for Tag {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Tag")
-            .field("typ", &self.typ)
-            .field("typ (numeric)", &(self.typ as u32))
+            .field("typ", &self.typ())
+            .field("typ (numeric)", &self.typ.val())
             .field("size", &(self.size))
             .finish()
     }
 }
 
+/// Common interface of all tag structures that can be looked up with
+/// [`crate::BootInformation::get_tag`]. It can also be implemented for custom tags,
+/// i.e. tags that are not part of the Multiboot2 specification or not modelled by this
+/// crate.
+///
+/// ## Example
+///
+/// ```ignore
+/// use multiboot2::{TagTrait, TagType, TagTypeId};
+///
+/// #[repr(C)]
+/// struct CustomTag {
+///     typ: TagTypeId,
+///     size: u32,
+///     foo: u32,
+/// }
+///
+/// unsafe impl TagTrait for CustomTag {
+///     const ID: TagType = TagType::Unknown(0x1337);
+/// }
+///
+/// let tag = boot_info.get_tag::<CustomTag>().unwrap();
+/// ```
+///
+/// ## Safety
+/// Implementors must guarantee that the type is `#[repr(C)]` (or `#[repr(C, packed)]`),
+/// starts with the `typ` and `size` fields of [`Tag`], has no alignment bigger than 8
+/// and that every bit pattern of the first [`TagTrait::MIN_SIZE`] bytes of a tag of type
+/// [`TagTrait::ID`] is a valid value of the type.
+pub unsafe trait TagTrait: Sized {
+    /// The type of the tag. The first tag of the MBI with this type is used.
+    const ID: TagType;
+
+    /// The minimum size of the tag in bytes, including the `typ` and `size` fields.
+    /// Tags that are smaller are ignored. Defaults to the size of the type.
+    const MIN_SIZE: usize = size_of::<Self>();
+}
+
+/// Iterates the tags of the MBI from the first tag until the end tag. Every tag
+/// is checked against the end of the MBI, so that a corrupt `size` field can't
+/// lead the iterator into unrelated memory. Tags of an unknown type are returned
+/// like all other tags.
 #[derive(Clone, Debug)]
 pub struct TagIter<'a> {
-    pub current: *const Tag,
+    current: *const Tag,
+    /// The address of the first byte after the MBI.
+    end: usize,
     phantom: PhantomData<&'a Tag>,
 }
 
 impl<'a> TagIter<'a> {
-    pub fn new(first: *const Tag) -> Self {
+    pub(crate) fn new(first: *const Tag, end: usize) -> Self {
         TagIter {
             current: first,
+            end,
             phantom: PhantomData,
         }
     }
@@ -143,20 +369,28 @@ impl<'a> Iterator for TagIter<'a> {
     type Item = &'a Tag;
 
     fn next(&mut self) -> Option<&'a Tag> {
-        match unsafe { &*self.current } {
-            &Tag {
-                typ: TagType::End,
-                size: 8,
-            } => None, // end tag
-            tag => {
-                // go to next tag
-                let mut tag_addr = self.current as usize;
-                tag_addr += ((tag.size + 7) & !7) as usize; //align at 8 byte
-                self.current = tag_addr as *const _;
-
-                Some(tag)
-            }
+        let tag_addr = self.current as usize;
+        // the tag header must be within the MBI
+        if tag_addr + size_of::<Tag>() > self.end {
+            return None;
+        }
+        let tag = unsafe { &*self.current };
+        if tag.typ == TagType::End && tag.size == 8 {
+            // end tag
+            return None;
         }
+
+        // the whole tag must be within the MBI, otherwise it is corrupt
+        let tag_end = tag_addr.checked_add(tag.size as usize)?;
+        if (tag.size as usize) < size_of::<Tag>() || tag_end > self.end {
+            return None;
+        }
+
+        // go to next tag
+        let next_tag_addr = tag_addr + ((tag.size as usize + 7) & !7); //align at 8 byte
+        self.current = next_tag_addr as *const _;
+
+        Some(tag)
     }
 }
 
@@ -197,4 +431,21 @@ mod tests {
         assert_eq!(21, TagType::LoadBaseAddr);
         assert_eq!(TagType::LoadBaseAddr, 21);
     }
+
+    /// Tests the conversion between raw values and the enum representation.
+    #[test]
+    fn test_conversion() {
+        for val in 0..=21 {
+            let typ = TagType::from(val);
+            assert!(!matches!(typ, TagType::Unknown(_)));
+            assert_eq!(u32::from(typ), val);
+            assert_eq!(TagType::from(TagTypeId::new(val)), typ);
+            assert_eq!(TagTypeId::from(typ), TagTypeId::new(val));
+        }
+        assert_eq!(TagType::from(22), TagType::Unknown(22));
+        assert_eq!(u32::from(TagType::Unknown(0x1337)), 0x1337);
+        // known types that are constructed as unknown types are still equal
+        assert_eq!(TagType::Unknown(1), TagType::Cmdline);
+        assert_eq!(TagTypeId::new(9), TagType::ElfSections);
+    }
 }
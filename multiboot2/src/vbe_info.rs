@@ -1,4 +1,4 @@
-use crate::TagType;
+use crate::{TagTrait, TagType, TagTypeId};
 use core::fmt;
 
 /// This tag contains VBE metadata, VBE controller information returned by the
@@ -6,7 +6,7 @@ use core::fmt;
 #[derive(Debug, Copy, Clone)]
 #[repr(C, packed)]
 pub struct VBEInfoTag {
-    typ: TagType,
+    typ: TagTypeId,
     length: u32,
 
     /// Indicates current video mode in the format specified in VBE 3.0.
@@ -37,6 +37,10 @@ pub struct VBEInfoTag {
     pub mode_info: VBEModeInfo,
 }
 
+unsafe impl TagTrait for VBEInfoTag {
+    const ID: TagType = TagType::Vbe;
+}
+
 /// VBE controller information.
 ///
 /// The capabilities of the display controller, the revision level of the